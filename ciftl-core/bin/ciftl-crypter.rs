@@ -10,8 +10,7 @@ use clap::Parser;
 
 use prettytable::{Cell, Row, Table};
 
-use ciftl_core::crypter::chacha20::ChaCha20CipherAlgorithm;
-use ciftl_core::crypter::StringCrypter;
+use ciftl_core::crypter::CipherAlgorithm;
 use ciftl_core::crypter::StringCrypterTrait;
 use ciftl_core::crypter::StringCrypterTrait as _;
 use ciftl_core::*;
@@ -61,10 +60,11 @@ fn main() {
         "csv" => FormatModeEnum::CSV,
         _ => panic!("Invalid format mode!"),
     };
-    // 目前只支持ChaCha20
-    let str_crypter: Box<dyn StringCrypterTrait> = match algorithm {
-        "ChaCha20" => Box::new(StringCrypter::<ChaCha20CipherAlgorithm>::default()),
-        _ => panic!("Invalid cipher algorithm!"),
+    // 目前支持ChaCha20、XChaCha20、AES256CTR、AES128OFB、AES256OFB、SM4（OFB模式，使用SM3校验），以及带认证的ChaCha20Poly1305和AES256GCM
+    // 算法名称忽略大小写以及'-'和'_'
+    let str_crypter: Box<dyn StringCrypterTrait> = match algorithm.parse::<CipherAlgorithm>() {
+        Ok(algorithm) => algorithm.new_string_crypter(),
+        Err(_) => panic!("Invalid cipher algorithm!"),
     };
     // 获取输入内容
     let mut content = String::new();
@@ -0,0 +1,184 @@
+use std::fs::read_to_string;
+use std::io;
+use std::io::Read;
+use std::process::exit;
+
+use clap::Parser;
+
+use ciftl_core::hash::checksum::{
+    check_manifest_with_options, ChecksumCheckOptions, ChecksumEntry, ChecksumFormat,
+    ChecksumReport, ChecksumStatus,
+};
+use ciftl_core::hash::HashAlgorithm;
+use ciftl_core::*;
+
+/// 所有文件都通过校验
+const EXIT_OK: i32 = 0;
+/// 至少有一个文件的摘要不一致
+const EXIT_CHECKSUM_MISMATCH: i32 = 1;
+/// 有文件不存在或无法读取、清单中有格式不正确的行，或参数不合法
+const EXIT_ERROR: i32 = 2;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Hash Algorithm
+    #[arg(short = 'a', long = "algorithm", default_value_t = String::from("sha256"))]
+    pub algorithm: String,
+
+    /// Read checksums from the files and check them
+    #[arg(short = 'c', long = "check")]
+    pub check: bool,
+
+    /// Create a BSD-style checksum
+    #[arg(long = "tag")]
+    pub tag: bool,
+
+    /// Don't print OK for each successfully verified file
+    #[arg(long = "quiet")]
+    pub quiet: bool,
+
+    /// Don't fail or report status for missing files
+    #[arg(long = "ignore-missing")]
+    pub ignore_missing: bool,
+
+    /// Files, "-" means standard input
+    pub files: Vec<String>,
+}
+
+/// 读取文件或标准输入的全部内容
+fn read_input(file: &str) -> io::Result<String> {
+    if file == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        return Ok(content);
+    }
+    read_to_string(file)
+}
+
+/// 计算文件的摘要并按照格式输出，返回退出码
+fn generate(algorithm: HashAlgorithm, files: &[String], format: ChecksumFormat) -> i32 {
+    let mut exit_code = EXIT_OK;
+    for file in files {
+        let entry = if file == "-" {
+            algorithm
+                .hash_reader(&mut io::stdin())
+                .map(|digest| ChecksumEntry {
+                    algorithm,
+                    digest,
+                    path: file.clone(),
+                    binary: false,
+                })
+        } else {
+            ChecksumEntry::from_file(algorithm, file)
+        };
+        match entry {
+            Ok(entry) => println!("{}", entry.to_line(format)),
+            Err(e) => {
+                eprintln!("ciftl-hash: {file}: {e}");
+                exit_code = EXIT_ERROR;
+            }
+        }
+    }
+    exit_code
+}
+
+/// 校验清单中的文件并输出每个文件的结果，返回退出码
+fn check(algorithm: HashAlgorithm, manifests: &[String], args: &Args) -> i32 {
+    let options = ChecksumCheckOptions {
+        ignore_missing: args.ignore_missing,
+    };
+    let mut total = ChecksumReport::default();
+    let mut unreadable_manifest_count = 0;
+    for manifest in manifests {
+        let content = match read_input(manifest) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("ciftl-hash: {manifest}: {e}");
+                unreadable_manifest_count += 1;
+                continue;
+            }
+        };
+        let report = check_manifest_with_options(&content, algorithm, options);
+        for (line_number, e) in &report.invalid_lines {
+            eprintln!("ciftl-hash: {manifest}: {line_number}: {e}");
+        }
+        for (entry, status) in &report.results {
+            match status {
+                ChecksumStatus::Ok => {
+                    if !args.quiet {
+                        println!("{}: OK", entry.path);
+                    }
+                }
+                ChecksumStatus::Failed => println!("{}: FAILED", entry.path),
+                ChecksumStatus::Missing => {
+                    eprintln!("ciftl-hash: {}: No such file or directory", entry.path);
+                    println!("{}: FAILED open or read", entry.path);
+                }
+                ChecksumStatus::Unreadable(e) => {
+                    eprintln!("ciftl-hash: {}: {e}", entry.path);
+                    println!("{}: FAILED open or read", entry.path);
+                }
+            }
+        }
+        total.results.extend(report.results);
+        total.invalid_lines.extend(report.invalid_lines);
+    }
+    let failed_count = total.failed_count();
+    let missing_count = total.missing_count() + unreadable_manifest_count;
+    let invalid_line_count = total.invalid_lines.len();
+    if invalid_line_count > 0 {
+        eprintln!("ciftl-hash: WARNING: {invalid_line_count} line(s) improperly formatted");
+    }
+    if missing_count > 0 {
+        eprintln!("ciftl-hash: WARNING: {missing_count} listed file(s) could not be read");
+    }
+    if failed_count > 0 {
+        eprintln!("ciftl-hash: WARNING: {failed_count} computed checksum(s) did NOT match");
+        return EXIT_CHECKSUM_MISMATCH;
+    }
+    if missing_count > 0 || invalid_line_count > 0 {
+        return EXIT_ERROR;
+    }
+    if total.verified_count() == 0 {
+        eprintln!("ciftl-hash: no file was verified");
+        return EXIT_ERROR;
+    }
+    EXIT_OK
+}
+
+fn main() {
+    let args = Args::parse();
+    // 名称忽略大小写以及'-'和'_'，如sha256、SHA-256、sm3、blake2b512
+    let algorithm = match args.algorithm.parse::<HashAlgorithm>() {
+        Ok(algorithm) => algorithm,
+        Err(e) => {
+            eprintln!("ciftl-hash: {e}");
+            exit(EXIT_ERROR);
+        }
+    };
+    if !algorithm.is_enabled() {
+        eprintln!(
+            "ciftl-hash: {}",
+            HASH_ALGORITHM_NOT_ENABLED.add_opt_mess(algorithm.name())
+        );
+        exit(EXIT_ERROR);
+    }
+    // 没有指定文件时读取标准输入
+    let files = if args.files.is_empty() {
+        vec![String::from("-")]
+    } else {
+        args.files.clone()
+    };
+    let exit_code = if args.check {
+        check(algorithm, &files, &args)
+    } else {
+        let format = if args.tag {
+            ChecksumFormat::Bsd
+        } else {
+            ChecksumFormat::Gnu
+        };
+        generate(algorithm, &files, format)
+    };
+    exit(exit_code);
+}
@@ -0,0 +1,298 @@
+use std::marker;
+
+use ::aes_gcm::Aes256Gcm as ExAes256Gcm;
+use ::chacha20poly1305::aead::{Aead, KeyInit};
+use ::chacha20poly1305::ChaCha20Poly1305 as ExChaCha20Poly1305;
+
+use crate::crypter::kdf::LegacySha256Kdf;
+use crate::crypter::{
+    decrypt_string, encrypt_string, rand_iv, AeadAlgorithmTrait, BytesCrypterTrait,
+    KeyDerivationTrait, KeyNewTrait, StringCrypterTrait,
+};
+use crate::*;
+
+/// ChaCha20Poly1305的Nonce长度
+pub const CHACHA20POLY1305_NONCE_LENGTH: usize = 12;
+/// ChaCha20Poly1305的Key长度
+pub const CHACHA20POLY1305_KEY_LENGTH: usize = 32;
+/// ChaCha20Poly1305的认证标签长度
+pub const CHACHA20POLY1305_TAG_LENGTH: usize = 16;
+
+/// AES256GCM的Nonce长度
+pub const AES256GCM_NONCE_LENGTH: usize = 12;
+/// AES256GCM的Key长度
+pub const AES256GCM_KEY_LENGTH: usize = 32;
+/// AES256GCM的认证标签长度
+pub const AES256GCM_TAG_LENGTH: usize = 16;
+
+pub struct ChaCha20Poly1305AeadAlgorithm {
+    m_algo: ExChaCha20Poly1305,
+}
+
+impl AeadAlgorithmTrait for ChaCha20Poly1305AeadAlgorithm {
+    const NONCE_LENGTH: usize = CHACHA20POLY1305_NONCE_LENGTH;
+    const KEY_LENGTH: usize = CHACHA20POLY1305_KEY_LENGTH;
+    const TAG_LENGTH: usize = CHACHA20POLY1305_TAG_LENGTH;
+
+    fn seal(&self, nonce: &[u8], data: &[u8]) -> Result<ByteVector> {
+        if nonce.len() != Self::NONCE_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        self.m_algo
+            .encrypt(nonce.into(), data)
+            .map_err(|e| FAILED_WHEN_AEAD_ENCRYPTING.add_opt_mess(&format!("{e}")))
+    }
+
+    fn open(&self, nonce: &[u8], data: &[u8]) -> Result<ByteVector> {
+        if nonce.len() != Self::NONCE_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        self.m_algo
+            .decrypt(nonce.into(), data)
+            .map_err(|_| FAILED_WHEN_AUTHENTICATING_CIPHERTEXT.clone())
+    }
+}
+
+impl KeyNewTrait for ChaCha20Poly1305AeadAlgorithm {
+    fn new(key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if key.len() != Self::KEY_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+        }
+        let algo = ExChaCha20Poly1305::new(key.into());
+        Ok(ChaCha20Poly1305AeadAlgorithm { m_algo: algo })
+    }
+}
+
+pub struct Aes256GcmAeadAlgorithm {
+    m_algo: ExAes256Gcm,
+}
+
+impl AeadAlgorithmTrait for Aes256GcmAeadAlgorithm {
+    const NONCE_LENGTH: usize = AES256GCM_NONCE_LENGTH;
+    const KEY_LENGTH: usize = AES256GCM_KEY_LENGTH;
+    const TAG_LENGTH: usize = AES256GCM_TAG_LENGTH;
+
+    fn seal(&self, nonce: &[u8], data: &[u8]) -> Result<ByteVector> {
+        if nonce.len() != Self::NONCE_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        self.m_algo
+            .encrypt(nonce.into(), data)
+            .map_err(|e| FAILED_WHEN_AEAD_ENCRYPTING.add_opt_mess(&format!("{e}")))
+    }
+
+    fn open(&self, nonce: &[u8], data: &[u8]) -> Result<ByteVector> {
+        if nonce.len() != Self::NONCE_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        self.m_algo
+            .decrypt(nonce.into(), data)
+            .map_err(|_| FAILED_WHEN_AUTHENTICATING_CIPHERTEXT.clone())
+    }
+}
+
+impl KeyNewTrait for Aes256GcmAeadAlgorithm {
+    fn new(key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if key.len() != Self::KEY_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+        }
+        let algo = ExAes256Gcm::new(key.into());
+        Ok(Aes256GcmAeadAlgorithm { m_algo: algo })
+    }
+}
+
+/// AeadBytesCrypter是基于认证加密的字节加密器，输出为salt || nonce || 密文 || 认证标签
+/// 与BytesCrypter不同，它用认证标签代替CRC32C校验值，可以发现对密文的篡改
+pub struct AeadBytesCrypter<
+    AA: AeadAlgorithmTrait + KeyNewTrait,
+    KD: KeyDerivationTrait + Default = LegacySha256Kdf,
+> {
+    _aa: marker::PhantomData<AA>,
+    /// 密钥派生算法
+    m_kdf: KD,
+}
+
+impl<AA: AeadAlgorithmTrait + KeyNewTrait, KD: KeyDerivationTrait + Default>
+    AeadBytesCrypter<AA, KD>
+{
+    /// 使用指定参数的密钥派生算法创建字节加密器
+    pub fn with_kdf(kdf: KD) -> Self {
+        AeadBytesCrypter::<AA, KD> {
+            _aa: marker::PhantomData::<AA>,
+            m_kdf: kdf,
+        }
+    }
+}
+
+impl<AA: AeadAlgorithmTrait + KeyNewTrait, KD: KeyDerivationTrait + Default> Default
+    for AeadBytesCrypter<AA, KD>
+{
+    fn default() -> Self {
+        Self::with_kdf(KD::default())
+    }
+}
+
+impl<AA: AeadAlgorithmTrait + KeyNewTrait, KD: KeyDerivationTrait + Default> BytesCrypterTrait
+    for AeadBytesCrypter<AA, KD>
+{
+    fn encrypt(&self, data: &[u8], password: &str) -> Result<ByteVector> {
+        let salt = rand_iv(KD::SALT_LENGTH);
+        let nonce = rand_iv(AA::NONCE_LENGTH);
+        let key = self.m_kdf.derive_key(password, &salt, AA::KEY_LENGTH)?;
+        let cipher_data_bytes = AA::new(&key)?.seal(&nonce, data)?;
+        Ok([&salt[..], &nonce[..], &cipher_data_bytes[..]].concat())
+    }
+
+    fn decrypt(&self, data: &[u8], password: &str) -> Result<ByteVector> {
+        // 从原文中获取数据
+        let mut salt = vec![0u8; KD::SALT_LENGTH];
+        let mut nonce = vec![0u8; AA::NONCE_LENGTH];
+        let mut mt = MemoryTaker::new(data);
+        let cipher_data_bytes = mt.take(&mut salt)?.take(&mut nonce)?.take_all()?;
+        if cipher_data_bytes.len() < AA::TAG_LENGTH {
+            return Err(FAILED_WHEN_AUTHENTICATING_CIPHERTEXT.clone());
+        }
+        let key = self.m_kdf.derive_key(password, &salt, AA::KEY_LENGTH)?;
+        AA::new(&key)?.open(&nonce, &cipher_data_bytes)
+    }
+}
+
+/// AeadStringCrypter是AeadBytesCrypter的一层包装，输出为AeadBytesCrypter结果的Base64编码
+pub struct AeadStringCrypter<
+    AA: AeadAlgorithmTrait + KeyNewTrait,
+    KD: KeyDerivationTrait + Default = LegacySha256Kdf,
+> {
+    m_bytes_crypter: AeadBytesCrypter<AA, KD>,
+}
+
+impl<AA: AeadAlgorithmTrait + KeyNewTrait, KD: KeyDerivationTrait + Default>
+    AeadStringCrypter<AA, KD>
+{
+    /// 使用指定参数的密钥派生算法创建文本加密器
+    pub fn with_kdf(kdf: KD) -> Self {
+        AeadStringCrypter::<AA, KD> {
+            m_bytes_crypter: AeadBytesCrypter::<AA, KD>::with_kdf(kdf),
+        }
+    }
+}
+
+impl<AA: AeadAlgorithmTrait + KeyNewTrait, KD: KeyDerivationTrait + Default> Default
+    for AeadStringCrypter<AA, KD>
+{
+    fn default() -> Self {
+        Self::with_kdf(KD::default())
+    }
+}
+
+impl<AA: AeadAlgorithmTrait + KeyNewTrait, KD: KeyDerivationTrait + Default> StringCrypterTrait
+    for AeadStringCrypter<AA, KD>
+{
+    fn encrypt(&self, data: &str, password: &str) -> Result<String> {
+        encrypt_string(&self.m_bytes_crypter, data, password)
+    }
+
+    fn decrypt(&self, data: &str, password: &str) -> Result<String> {
+        decrypt_string(&self.m_bytes_crypter, data, password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::encoding::base64::Base64Encoding;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_aead_string_crypter() {
+        let crypters: [Box<dyn StringCrypterTrait>; 2] = [
+            Box::new(AeadStringCrypter::<ChaCha20Poly1305AeadAlgorithm>::default()),
+            Box::new(AeadStringCrypter::<Aes256GcmAeadAlgorithm>::default()),
+        ];
+        let b64 = Base64Encoding::default();
+        for string_crypter in crypters {
+            let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+            let plaintext = string_crypter.decrypt(&ciphertext, "123456").unwrap();
+            assert_eq!(plaintext, "123456");
+            // 错误的密码
+            let res = string_crypter.decrypt(&ciphertext, "654321");
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+            // 篡改密文中的每一个字节都应该无法通过认证
+            let raw = b64.decode(&ciphertext).unwrap();
+            for i in 0..raw.len() {
+                let mut tampered = raw.clone();
+                tampered[i] ^= 0x01;
+                let res = string_crypter.decrypt(&b64.encode(&tampered), "123456");
+                assert_eq!(
+                    format!("{}", res.unwrap_err()),
+                    format!("{}", FAILED_WHEN_AUTHENTICATING_CIPHERTEXT)
+                );
+            }
+            // 截断的密文
+            assert!(string_crypter
+                .decrypt(&b64.encode(&raw[..raw.len() - 1]), "123456")
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_aead_bytes_crypter() {
+        let crypters: [Box<dyn BytesCrypterTrait>; 2] = [
+            Box::new(AeadBytesCrypter::<ChaCha20Poly1305AeadAlgorithm>::default()),
+            Box::new(AeadBytesCrypter::<Aes256GcmAeadAlgorithm>::default()),
+        ];
+        for bytes_crypter in crypters {
+            for plain_data in [vec![0xFF, 0xFE, 0x00, 0x80, 0xC3], vec![]] {
+                let cipher_data = bytes_crypter.encrypt(&plain_data, "123456").unwrap();
+                assert_eq!(
+                    bytes_crypter.decrypt(&cipher_data, "123456").unwrap(),
+                    plain_data
+                );
+                assert!(bytes_crypter.decrypt(&cipher_data, "654321").is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_chacha20poly1305_rfc8439_vector() {
+        // 使用RFC 8439 2.8.2中的Key和Nonce，不包含AAD
+        let hexe = HexEncoding::default();
+        let key = hexe
+            .decode("808182838485868788898A8B8C8D8E8F909192939495969798999A9B9C9D9E9F")
+            .unwrap();
+        let nonce = hexe.decode("070000004041424344454647").unwrap();
+        let algo = ChaCha20Poly1305AeadAlgorithm::new(&key).unwrap();
+        let sealed = algo.seal(&nonce, b"123456").unwrap();
+        assert_eq!(
+            "AE49DA6934CB2EBCCCF92E4B42B12306575FDAE09DC6".to_string(),
+            hexe.encode(&sealed)
+        );
+        assert_eq!(algo.open(&nonce, &sealed).unwrap(), b"123456".to_vec());
+    }
+
+    #[test]
+    fn test_aes256gcm_vector() {
+        // GCM规范测试用例13：全零的Key和Nonce，空明文
+        let hexe = HexEncoding::default();
+        let algo = Aes256GcmAeadAlgorithm::new(&[0u8; 32]).unwrap();
+        let sealed = algo.seal(&[0u8; 12], b"").unwrap();
+        assert_eq!(
+            "530F8AFBC74536B9A963B4F1C4CB738B".to_string(),
+            hexe.encode(&sealed)
+        );
+        // GCM规范测试用例14：全零的Key、Nonce和一个分组的明文
+        let sealed = algo.seal(&[0u8; 12], &[0u8; 16]).unwrap();
+        assert_eq!(
+            "CEA7403D4D606B6E074EC5D3BAF39D18D0D1C8A799996BF0265B98B5D48AB919".to_string(),
+            hexe.encode(&sealed)
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use ::aes::cipher::consts::U16;
+use ::aes::cipher::typenum::Unsigned;
+use ::aes::cipher::{Block, BlockDecrypt, BlockEncrypt, KeyInit, KeySizeUser};
+use ::aes::{Aes128 as ExAes128, Aes256 as ExAes256};
+use ::ctr::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use ::ctr::Ctr128BE;
+
+use crate::crypter::{
+    BlockCipherAlgorithmTrait, CipherAlgorithmBaseTrait, CipherAlgorithmTrait, CipherAlgorithmType,
+    IVKeyNewTrait, KeyNewTrait, SeekableCipherAlgorithmTrait,
+};
+use crate::*;
+
+/// AES的分组长度
+pub const AES_BLOCK_LENGTH: usize = 16;
+/// AES256CTR的IV长度
+pub const AES256CTR_IV_LENGTH: usize = AES_BLOCK_LENGTH;
+/// AES256CTR的Key长度
+pub const AES256CTR_KEY_LENGTH: usize = 32;
+
+/// AESOFB的IV长度
+pub const AESOFB_IV_LENGTH: usize = AES_BLOCK_LENGTH;
+
+/// AES256CTR，计数器为IV的大端128位整数
+pub struct Aes256CtrCipherAlgorithm {
+    m_algo: Ctr128BE<ExAes256>,
+}
+
+impl CipherAlgorithmBaseTrait for Aes256CtrCipherAlgorithm {
+    const IV_LENGTH: usize = AES256CTR_IV_LENGTH;
+    const KEY_LENGTH: usize = AES256CTR_KEY_LENGTH;
+    // CTR模式下AES被当作流密码使用
+    const CIPHER_ALGORITHM_TYPE: CipherAlgorithmType = CipherAlgorithmType::Stream;
+}
+
+impl CipherAlgorithmTrait for Aes256CtrCipherAlgorithm {
+    fn crypt(&mut self, src_data: &[u8], dst_data: &mut [u8]) -> Result<()> {
+        // 这里为了减少拷贝次数，先将src复制到dst中
+        memcpy(dst_data, src_data)?;
+        self.m_algo.apply_keystream(dst_data);
+        Ok(())
+    }
+}
+
+impl SeekableCipherAlgorithmTrait for Aes256CtrCipherAlgorithm {
+    fn seek(&mut self, pos: u64) -> Result<()> {
+        self.m_algo
+            .try_seek(pos)
+            .map_err(|e| FAILED_WHEN_SEEKING_STREAM.add_opt_mess(&format!("{e}")))
+    }
+}
+
+impl IVKeyNewTrait for Aes256CtrCipherAlgorithm {
+    fn new(iv: &[u8], key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if iv.len() != Self::IV_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        if key.len() != Self::KEY_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+        }
+        let cipher = Ctr128BE::<ExAes256>::new(key.into(), iv.into());
+        Ok(Aes256CtrCipherAlgorithm { m_algo: cipher })
+    }
+}
+
+/// AES的OFB模式，以分组密码的方式接入StreamGenerator
+/// 每次对反馈寄存器加密得到一个分组的密码流，因此输入的长度必须为分组长度的整数倍
+pub struct AesOfbCipherAlgorithm<C: BlockEncrypt<BlockSize = U16> + KeyInit> {
+    m_algo: C,
+    /// 反馈寄存器，初始值为IV
+    m_feedback: Block<C>,
+}
+
+/// AES128OFB
+pub type Aes128OfbCipherAlgorithm = AesOfbCipherAlgorithm<ExAes128>;
+/// AES256OFB
+pub type Aes256OfbCipherAlgorithm = AesOfbCipherAlgorithm<ExAes256>;
+
+impl<C: BlockEncrypt<BlockSize = U16> + KeyInit> CipherAlgorithmBaseTrait
+    for AesOfbCipherAlgorithm<C>
+{
+    const IV_LENGTH: usize = AESOFB_IV_LENGTH;
+    const KEY_LENGTH: usize = <C as KeySizeUser>::KeySize::USIZE;
+    const CIPHER_ALGORITHM_TYPE: CipherAlgorithmType = CipherAlgorithmType::Block(AES_BLOCK_LENGTH);
+}
+
+impl<C: BlockEncrypt<BlockSize = U16> + KeyInit> CipherAlgorithmTrait for AesOfbCipherAlgorithm<C> {
+    fn crypt(&mut self, src_data: &[u8], dst_data: &mut [u8]) -> Result<()> {
+        if !src_data.len().is_multiple_of(AES_BLOCK_LENGTH) {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        memcpy(dst_data, src_data)?;
+        for block in dst_data.chunks_mut(AES_BLOCK_LENGTH) {
+            // O_i = E(K, O_{i-1})，C_i = P_i ^ O_i
+            self.m_algo.encrypt_block(&mut self.m_feedback);
+            for (d, k) in block.iter_mut().zip(self.m_feedback.iter()) {
+                *d ^= *k;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C: BlockEncrypt<BlockSize = U16> + KeyInit> IVKeyNewTrait for AesOfbCipherAlgorithm<C> {
+    fn new(iv: &[u8], key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if iv.len() != Self::IV_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        if key.len() != Self::KEY_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+        }
+        let cipher = C::new_from_slice(key)
+            .map_err(|e| CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.add_opt_mess(&format!("{e}")))?;
+        Ok(AesOfbCipherAlgorithm::<C> {
+            m_algo: cipher,
+            m_feedback: Block::<C>::clone_from_slice(iv),
+        })
+    }
+}
+
+/// AES分组密码本身，供BlockCrypter以ECB、CBC、CFB等模式使用
+pub struct AesBlockCipherAlgorithm<C: BlockEncrypt<BlockSize = U16> + BlockDecrypt + KeyInit> {
+    m_algo: C,
+}
+
+/// AES128
+pub type Aes128BlockCipherAlgorithm = AesBlockCipherAlgorithm<ExAes128>;
+/// AES256
+pub type Aes256BlockCipherAlgorithm = AesBlockCipherAlgorithm<ExAes256>;
+
+impl<C: BlockEncrypt<BlockSize = U16> + BlockDecrypt + KeyInit> CipherAlgorithmBaseTrait
+    for AesBlockCipherAlgorithm<C>
+{
+    // 分组模式下IV的长度与分组长度相同
+    const IV_LENGTH: usize = AES_BLOCK_LENGTH;
+    const KEY_LENGTH: usize = <C as KeySizeUser>::KeySize::USIZE;
+    const CIPHER_ALGORITHM_TYPE: CipherAlgorithmType = CipherAlgorithmType::Block(AES_BLOCK_LENGTH);
+}
+
+impl<C: BlockEncrypt<BlockSize = U16> + BlockDecrypt + KeyInit> BlockCipherAlgorithmTrait
+    for AesBlockCipherAlgorithm<C>
+{
+    fn encrypt_block(&self, block: &mut [u8]) -> Result<()> {
+        if block.len() != AES_BLOCK_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        self.m_algo.encrypt_block(Block::<C>::from_mut_slice(block));
+        Ok(())
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> Result<()> {
+        if block.len() != AES_BLOCK_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        self.m_algo.decrypt_block(Block::<C>::from_mut_slice(block));
+        Ok(())
+    }
+}
+
+impl<C: BlockEncrypt<BlockSize = U16> + BlockDecrypt + KeyInit> KeyNewTrait
+    for AesBlockCipherAlgorithm<C>
+{
+    fn new(key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if key.len() != Self::KEY_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+        }
+        let cipher = C::new_from_slice(key)
+            .map_err(|e| CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.add_opt_mess(&format!("{e}")))?;
+        Ok(AesBlockCipherAlgorithm::<C> { m_algo: cipher })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::crypter::{StringCrypter, StringCrypterTrait};
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_aes256ctr() {
+        let string_crypter = StringCrypter::<Aes256CtrCipherAlgorithm>::default();
+        let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+        println!("ciphertext: {ciphertext}");
+        let plaintext = string_crypter.decrypt(&ciphertext, "123456").unwrap();
+        println!("plaintext: {plaintext}");
+        assert_eq!(plaintext, "123456");
+    }
+
+    #[test]
+    fn test_aes256ctr_nist_vector() {
+        // NIST SP 800-38A F.5.5 CTR-AES256.Encrypt
+        let hexe = HexEncoding::default();
+        let key = hexe
+            .decode("603DEB1015CA71BE2B73AEF0857D77811F352C073B6108D72D9810A30914DFF4")
+            .unwrap();
+        let iv = hexe.decode("F0F1F2F3F4F5F6F7F8F9FAFBFCFDFEFF").unwrap();
+        let plaintext = hexe
+            .decode("6BC1BEE22E409F96E93D7E117393172AAE2D8A571E03AC9C9EB76FAC45AF8E51")
+            .unwrap();
+        let mut ciphertext = vec![0u8; plaintext.len()];
+        let mut algo = Aes256CtrCipherAlgorithm::new(&iv, &key).unwrap();
+        algo.crypt(&plaintext, &mut ciphertext).unwrap();
+        assert_eq!(
+            "601EC313775789A5B7A7F504BBF3D228F443E3CA4D62B59ACA84E990CACAF5C5".to_string(),
+            hexe.encode(&ciphertext)
+        );
+    }
+
+    #[test]
+    fn test_aes_ofb() {
+        let string_crypter = StringCrypter::<Aes128OfbCipherAlgorithm>::default();
+        let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+        let plaintext = string_crypter.decrypt(&ciphertext, "123456").unwrap();
+        assert_eq!(plaintext, "123456");
+        let string_crypter = StringCrypter::<Aes256OfbCipherAlgorithm>::default();
+        let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+        let plaintext = string_crypter.decrypt(&ciphertext, "123456").unwrap();
+        assert_eq!(plaintext, "123456");
+    }
+
+    #[test]
+    fn test_aes_ofb_nist_vector() {
+        // NIST SP 800-38A F.4.1 OFB-AES128.Encrypt 和 F.4.5 OFB-AES256.Encrypt
+        let hexe = HexEncoding::default();
+        let iv = hexe.decode("000102030405060708090A0B0C0D0E0F").unwrap();
+        let plaintext = hexe
+            .decode("6BC1BEE22E409F96E93D7E117393172AAE2D8A571E03AC9C9EB76FAC45AF8E51")
+            .unwrap();
+        let mut ciphertext = vec![0u8; plaintext.len()];
+        let key = hexe.decode("2B7E151628AED2A6ABF7158809CF4F3C").unwrap();
+        let mut algo = Aes128OfbCipherAlgorithm::new(&iv, &key).unwrap();
+        algo.crypt(&plaintext, &mut ciphertext).unwrap();
+        assert_eq!(
+            "3B3FD92EB72DAD20333449F8E83CFB4A7789508D16918F03F53C52DAC54ED825".to_string(),
+            hexe.encode(&ciphertext)
+        );
+        let key = hexe
+            .decode("603DEB1015CA71BE2B73AEF0857D77811F352C073B6108D72D9810A30914DFF4")
+            .unwrap();
+        let mut algo = Aes256OfbCipherAlgorithm::new(&iv, &key).unwrap();
+        algo.crypt(&plaintext, &mut ciphertext).unwrap();
+        assert_eq!(
+            "DC7E84BFDA79164B7ECD8486985D38604FEBDC6740D20B3AC88F6AD82A4FB08D".to_string(),
+            hexe.encode(&ciphertext)
+        );
+        // 不足一个分组时报错
+        assert!(algo.crypt(&plaintext[..15], &mut ciphertext[..15]).is_err());
+    }
+}
@@ -0,0 +1,389 @@
+use std::marker;
+
+use crate::crypter::{BlockCipherAlgorithmTrait, CipherAlgorithmType, KeyNewTrait};
+use crate::*;
+
+/// 分组密码的工作模式
+#[derive(Clone)]
+pub enum BlockCipherMode {
+    /// 电子密码本模式，不需要IV
+    ECB,
+    /// 密码分组链接模式
+    CBC,
+    /// 密文反馈模式，反馈长度为一个分组
+    CFB,
+}
+
+/// 填充方式
+#[derive(Clone)]
+pub enum PaddingScheme {
+    /// 不填充，ECB和CBC模式下要求数据长度为分组长度的整数倍
+    NoPadding,
+    /// PKCS#7，填充N个值为N的字节
+    Pkcs7,
+    /// ISO/IEC 7816-4，填充一个0x80，其余补0x00
+    Iso7816,
+    /// ANSI X9.23，补0x00，最后一个字节为填充长度
+    AnsiX923,
+    /// 补0x00直到分组长度的整数倍，若已对齐则不填充，解密时会去掉末尾所有的0x00
+    ZeroPadding,
+}
+
+impl PaddingScheme {
+    /// 对数据进行填充
+    pub fn pad(&self, data: &[u8], block_length: usize) -> ByteVector {
+        let mut res = data.to_vec();
+        // 需要填充的长度
+        let pad_length = block_length - data.len() % block_length;
+        match self {
+            PaddingScheme::NoPadding => {}
+            PaddingScheme::Pkcs7 => {
+                res.resize(data.len() + pad_length, pad_length as u8);
+            }
+            PaddingScheme::Iso7816 => {
+                res.push(0x80);
+                res.resize(data.len() + pad_length, 0x00);
+            }
+            PaddingScheme::AnsiX923 => {
+                res.resize(data.len() + pad_length - 1, 0x00);
+                res.push(pad_length as u8);
+            }
+            PaddingScheme::ZeroPadding => {
+                if pad_length != block_length {
+                    res.resize(data.len() + pad_length, 0x00);
+                }
+            }
+        }
+        res
+    }
+
+    /// 去除数据的填充
+    pub fn unpad(&self, data: &[u8], block_length: usize) -> Result<ByteVector> {
+        let len = data.len();
+        let unpadded_length = match self {
+            PaddingScheme::NoPadding => len,
+            PaddingScheme::Pkcs7 => {
+                let pad_length = Self::last_byte_as_pad_length(data, block_length)?;
+                if data[len - pad_length..]
+                    .iter()
+                    .any(|b| *b as usize != pad_length)
+                {
+                    return Err(FAILED_WHEN_UNPADDING.add_opt_mess("PKCS#7"));
+                }
+                len - pad_length
+            }
+            PaddingScheme::Iso7816 => {
+                // 从末尾找到第一个非0x00的字节，它必须是0x80且位于最后一个分组中
+                let pos = data.iter().rposition(|b| *b != 0x00);
+                match pos {
+                    Some(pos) if data[pos] == 0x80 && len - pos <= block_length => pos,
+                    _ => return Err(FAILED_WHEN_UNPADDING.add_opt_mess("ISO/IEC 7816-4")),
+                }
+            }
+            PaddingScheme::AnsiX923 => {
+                let pad_length = Self::last_byte_as_pad_length(data, block_length)?;
+                if data[len - pad_length..len - 1].iter().any(|b| *b != 0x00) {
+                    return Err(FAILED_WHEN_UNPADDING.add_opt_mess("ANSI X9.23"));
+                }
+                len - pad_length
+            }
+            PaddingScheme::ZeroPadding => data
+                .iter()
+                .rposition(|b| *b != 0x00)
+                .map_or(0, |pos| pos + 1),
+        };
+        Ok(data[..unpadded_length].to_vec())
+    }
+
+    /// 读取最后一个字节作为填充长度，并检查其是否在[1, block_length]之间
+    fn last_byte_as_pad_length(data: &[u8], block_length: usize) -> Result<usize> {
+        let pad_length = match data.last() {
+            Some(b) => *b as usize,
+            None => return Err(FAILED_WHEN_UNPADDING.add_opt_mess("没有可以去除的填充")),
+        };
+        if pad_length == 0 || pad_length > block_length || pad_length > data.len() {
+            return Err(FAILED_WHEN_UNPADDING.add_opt_mess("填充长度不合法"));
+        }
+        Ok(pad_length)
+    }
+}
+
+/// BlockCrypter用指定的工作模式和填充方式驱动一个分组密码算法
+pub struct BlockCrypter<BC: BlockCipherAlgorithmTrait + KeyNewTrait> {
+    m_mode: BlockCipherMode,
+    m_padding: PaddingScheme,
+    _bc: marker::PhantomData<BC>,
+}
+
+impl<BC: BlockCipherAlgorithmTrait + KeyNewTrait> BlockCrypter<BC> {
+    pub fn new(mode: BlockCipherMode, padding: PaddingScheme) -> Self {
+        BlockCrypter::<BC> {
+            m_mode: mode,
+            m_padding: padding,
+            _bc: marker::PhantomData::<BC>,
+        }
+    }
+
+    /// 获取分组长度
+    fn block_length() -> Result<usize> {
+        match BC::cipher_algorithm_type() {
+            CipherAlgorithmType::Block(n) => Ok(n),
+            CipherAlgorithmType::Stream => {
+                Err(BLOCK_CRYPTER_REQUIRES_BLOCK_CIPHER_ALGORITHM.clone())
+            }
+        }
+    }
+
+    /// 检查IV的长度，ECB模式不使用IV
+    fn check_iv(&self, iv: &[u8], block_length: usize) -> Result<()> {
+        if let BlockCipherMode::ECB = self.m_mode {
+            return Ok(());
+        }
+        if iv.len() != block_length {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        Ok(())
+    }
+
+    /// 加密，ECB模式下iv会被忽略
+    pub fn encrypt(&self, iv: &[u8], key: &[u8], data: &[u8]) -> Result<ByteVector> {
+        let block_length = Self::block_length()?;
+        self.check_iv(iv, block_length)?;
+        let algo = BC::new(key)?;
+        let mut res = self.m_padding.pad(data, block_length);
+        match self.m_mode {
+            BlockCipherMode::ECB => {
+                Self::check_data_length(&res, block_length)?;
+                for block in res.chunks_mut(block_length) {
+                    algo.encrypt_block(block)?;
+                }
+            }
+            BlockCipherMode::CBC => {
+                Self::check_data_length(&res, block_length)?;
+                let mut feedback = iv.to_vec();
+                for block in res.chunks_mut(block_length) {
+                    // C_i = E(K, P_i ^ C_{i-1})
+                    xor_in_place(block, &feedback);
+                    algo.encrypt_block(block)?;
+                    memcpy(&mut feedback, block)?;
+                }
+            }
+            BlockCipherMode::CFB => {
+                let mut feedback = iv.to_vec();
+                for block in res.chunks_mut(block_length) {
+                    // C_i = P_i ^ E(K, C_{i-1})，最后一个分组可以不完整
+                    algo.encrypt_block(&mut feedback)?;
+                    xor_in_place(block, &feedback);
+                    feedback[..block.len()].copy_from_slice(block);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// 解密，ECB模式下iv会被忽略
+    pub fn decrypt(&self, iv: &[u8], key: &[u8], data: &[u8]) -> Result<ByteVector> {
+        let block_length = Self::block_length()?;
+        self.check_iv(iv, block_length)?;
+        let algo = BC::new(key)?;
+        let mut res = data.to_vec();
+        match self.m_mode {
+            BlockCipherMode::ECB => {
+                Self::check_data_length(&res, block_length)?;
+                for block in res.chunks_mut(block_length) {
+                    algo.decrypt_block(block)?;
+                }
+            }
+            BlockCipherMode::CBC => {
+                Self::check_data_length(&res, block_length)?;
+                let mut feedback = iv.to_vec();
+                let mut current = vec![0x00; block_length];
+                for block in res.chunks_mut(block_length) {
+                    // P_i = D(K, C_i) ^ C_{i-1}
+                    memcpy(&mut current, block)?;
+                    algo.decrypt_block(block)?;
+                    xor_in_place(block, &feedback);
+                    std::mem::swap(&mut feedback, &mut current);
+                }
+            }
+            BlockCipherMode::CFB => {
+                let mut feedback = iv.to_vec();
+                let mut current = vec![0x00; block_length];
+                for block in res.chunks_mut(block_length) {
+                    // P_i = C_i ^ E(K, C_{i-1})
+                    current[..block.len()].copy_from_slice(block);
+                    algo.encrypt_block(&mut feedback)?;
+                    xor_in_place(block, &feedback);
+                    std::mem::swap(&mut feedback, &mut current);
+                }
+            }
+        }
+        self.m_padding.unpad(&res, block_length)
+    }
+
+    /// 检查数据长度是否为分组长度的整数倍
+    fn check_data_length(data: &[u8], block_length: usize) -> Result<()> {
+        if !data.len().is_multiple_of(block_length) {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        Ok(())
+    }
+}
+
+impl<BC: BlockCipherAlgorithmTrait + KeyNewTrait> Default for BlockCrypter<BC> {
+    /// 默认使用CBC模式和PKCS#7填充
+    fn default() -> Self {
+        Self::new(BlockCipherMode::CBC, PaddingScheme::Pkcs7)
+    }
+}
+
+/// 将src异或到dst上，长度以dst为准
+#[inline]
+fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src.iter()) {
+        *d ^= *s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypter::aes::{Aes128BlockCipherAlgorithm, Aes256BlockCipherAlgorithm};
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_padding() {
+        let data = [0x01u8, 0x02, 0x03];
+        let cases = [
+            (PaddingScheme::Pkcs7, vec![1, 2, 3, 5, 5, 5, 5, 5]),
+            (PaddingScheme::Iso7816, vec![1, 2, 3, 0x80, 0, 0, 0, 0]),
+            (PaddingScheme::AnsiX923, vec![1, 2, 3, 0, 0, 0, 0, 5]),
+            (PaddingScheme::ZeroPadding, vec![1, 2, 3, 0, 0, 0, 0, 0]),
+            (PaddingScheme::NoPadding, vec![1, 2, 3]),
+        ];
+        for (padding, expected) in cases {
+            let padded = padding.pad(&data, 8);
+            assert_eq!(padded, expected);
+            assert_eq!(padding.unpad(&padded, 8).unwrap(), data.to_vec());
+        }
+        // 已对齐的数据也要填充一个完整的分组
+        assert_eq!(PaddingScheme::Pkcs7.pad(&[0u8; 8], 8).len(), 16);
+        assert_eq!(PaddingScheme::ZeroPadding.pad(&[1u8; 8], 8).len(), 8);
+        // 不合法的填充
+        assert!(PaddingScheme::Pkcs7
+            .unpad(&[1, 2, 3, 3, 3, 4, 4, 4], 8)
+            .is_err());
+        assert!(PaddingScheme::Pkcs7
+            .unpad(&[1, 2, 3, 3, 3, 4, 4, 0], 8)
+            .is_err());
+        assert!(PaddingScheme::Pkcs7
+            .unpad(&[1, 2, 3, 3, 3, 4, 4, 9], 8)
+            .is_err());
+        assert!(PaddingScheme::Pkcs7.unpad(&[], 8).is_err());
+        assert!(PaddingScheme::Iso7816
+            .unpad(&[1, 2, 3, 0, 0, 0, 0, 0], 8)
+            .is_err());
+        assert!(PaddingScheme::AnsiX923
+            .unpad(&[1, 2, 3, 0, 1, 0, 0, 5], 8)
+            .is_err());
+        let err = PaddingScheme::Pkcs7.unpad(&[2, 2, 2, 2, 2, 2, 1, 2], 8);
+        println!("错误：{}", err.unwrap_err());
+    }
+
+    #[test]
+    fn test_block_crypter_nist_vector() {
+        // NIST SP 800-38A F.1.1 ECB-AES128、F.2.1 CBC-AES128、F.3.13 CFB128-AES128
+        let hexe = HexEncoding::default();
+        let key = hexe.decode("2B7E151628AED2A6ABF7158809CF4F3C").unwrap();
+        let iv = hexe.decode("000102030405060708090A0B0C0D0E0F").unwrap();
+        let plaintext = hexe
+            .decode("6BC1BEE22E409F96E93D7E117393172AAE2D8A571E03AC9C9EB76FAC45AF8E51")
+            .unwrap();
+        let cases = [
+            (
+                BlockCipherMode::ECB,
+                "3AD77BB40D7A3660A89ECAF32466EF97F5D3D58503B9699DE785895A96FDBAAF",
+            ),
+            (
+                BlockCipherMode::CBC,
+                "7649ABAC8119B246CEE98E9B12E9197D5086CB9B507219EE95DB113A917678B2",
+            ),
+            (
+                BlockCipherMode::CFB,
+                "3B3FD92EB72DAD20333449F8E83CFB4AC8A64537A0B3A93FCDE3CDAD9F1CE58B",
+            ),
+        ];
+        for (mode, expected) in cases {
+            let crypter =
+                BlockCrypter::<Aes128BlockCipherAlgorithm>::new(mode, PaddingScheme::NoPadding);
+            let ciphertext = crypter.encrypt(&iv, &key, &plaintext).unwrap();
+            assert_eq!(expected.to_string(), hexe.encode(&ciphertext));
+            assert_eq!(crypter.decrypt(&iv, &key, &ciphertext).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_block_crypter_with_padding() {
+        let hexe = HexEncoding::default();
+        let key = hexe.decode("2B7E151628AED2A6ABF7158809CF4F3C").unwrap();
+        let iv = hexe.decode("000102030405060708090A0B0C0D0E0F").unwrap();
+        let plaintext = "Hello, ciftl! 你好！".as_bytes();
+        // CBC/PKCS#7
+        let crypter = BlockCrypter::<Aes128BlockCipherAlgorithm>::default();
+        let ciphertext = crypter.encrypt(&iv, &key, plaintext).unwrap();
+        assert_eq!(
+            "3AFA5C45834488826DDC3A53D18A4D636E33C06796C2FF96606D4EE3A6311AE2".to_string(),
+            hexe.encode(&ciphertext)
+        );
+        assert_eq!(crypter.decrypt(&iv, &key, &ciphertext).unwrap(), plaintext);
+        // CBC/ANSI X9.23
+        let crypter = BlockCrypter::<Aes128BlockCipherAlgorithm>::new(
+            BlockCipherMode::CBC,
+            PaddingScheme::AnsiX923,
+        );
+        let ciphertext = crypter.encrypt(&iv, &key, plaintext).unwrap();
+        assert_eq!(
+            "3AFA5C45834488826DDC3A53D18A4D638627A0437FFB94BC510A39E3148E6B52".to_string(),
+            hexe.encode(&ciphertext)
+        );
+        // CFB不填充时允许最后一个分组不完整
+        let crypter = BlockCrypter::<Aes128BlockCipherAlgorithm>::new(
+            BlockCipherMode::CFB,
+            PaddingScheme::NoPadding,
+        );
+        let ciphertext = crypter.encrypt(&iv, &key, plaintext).unwrap();
+        assert_eq!(
+            "189B0BA0F64112D5B36F4385BA8F08DDDC23025A673FCE".to_string(),
+            hexe.encode(&ciphertext)
+        );
+        assert_eq!(crypter.decrypt(&iv, &key, &ciphertext).unwrap(), plaintext);
+        // 各种模式和填充组合的往返
+        let key = vec![0x42u8; 32];
+        for mode in [
+            BlockCipherMode::ECB,
+            BlockCipherMode::CBC,
+            BlockCipherMode::CFB,
+        ] {
+            for padding in [
+                PaddingScheme::Pkcs7,
+                PaddingScheme::Iso7816,
+                PaddingScheme::AnsiX923,
+                PaddingScheme::ZeroPadding,
+            ] {
+                let crypter =
+                    BlockCrypter::<Aes256BlockCipherAlgorithm>::new(mode.clone(), padding);
+                let ciphertext = crypter.encrypt(&iv, &key, plaintext).unwrap();
+                assert_eq!(crypter.decrypt(&iv, &key, &ciphertext).unwrap(), plaintext);
+            }
+        }
+        // 错误的密钥导致填充不合法
+        let crypter = BlockCrypter::<Aes256BlockCipherAlgorithm>::default();
+        let ciphertext = crypter.encrypt(&iv, &key, plaintext).unwrap();
+        assert!(crypter.decrypt(&iv, &[0x24u8; 32], &ciphertext).is_err());
+        // 长度不是分组长度整数倍的密文
+        assert!(crypter.decrypt(&iv, &key, &ciphertext[1..]).is_err());
+        // 不合法的IV长度
+        assert!(crypter.encrypt(&iv[1..], &key, plaintext).is_err());
+    }
+}
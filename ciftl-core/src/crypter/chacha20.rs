@@ -1,9 +1,10 @@
 use ::chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
 use ::chacha20::ChaCha20 as ExChaCha20;
+use ::chacha20::XChaCha20 as ExXChaCha20;
 
 use crate::crypter::{
     CipherAlgorithmBaseTrait, CipherAlgorithmTrait, CipherAlgorithmType, IVKeyNewTrait,
-    StreamGenerator, StreamGeneratorMode, StringCrypter,
+    SeekableCipherAlgorithmTrait, StreamGenerator, StreamGeneratorMode, StringCrypter,
 };
 use crate::*;
 
@@ -12,6 +13,11 @@ pub const CHACHA20_IV_LENGTH: usize = 12;
 /// ChaCha20的Key长度
 pub const CHACHA20_KEY_LENGTH: usize = 32;
 
+/// XChaCha20的IV长度，随机生成时发生碰撞的概率可以忽略
+pub const XCHACHA20_IV_LENGTH: usize = 24;
+/// XChaCha20的Key长度
+pub const XCHACHA20_KEY_LENGTH: usize = 32;
+
 pub struct ChaCha20CipherAlgorithm {
     m_algo: ExChaCha20,
 }
@@ -31,6 +37,14 @@ impl CipherAlgorithmTrait for ChaCha20CipherAlgorithm {
     }
 }
 
+impl SeekableCipherAlgorithmTrait for ChaCha20CipherAlgorithm {
+    fn seek(&mut self, pos: u64) -> Result<()> {
+        self.m_algo
+            .try_seek(pos)
+            .map_err(|e| FAILED_WHEN_SEEKING_STREAM.add_opt_mess(&format!("{e}")))
+    }
+}
+
 impl IVKeyNewTrait for ChaCha20CipherAlgorithm {
     fn new(iv: &[u8], key: &[u8]) -> Result<Self>
     where
@@ -47,6 +61,50 @@ impl IVKeyNewTrait for ChaCha20CipherAlgorithm {
     }
 }
 
+/// XChaCha20，先用HChaCha20由Key和IV的前16字节派生子密钥，再以剩余8字节作为ChaCha20的Nonce
+pub struct XChaCha20CipherAlgorithm {
+    m_algo: ExXChaCha20,
+}
+
+impl CipherAlgorithmBaseTrait for XChaCha20CipherAlgorithm {
+    const IV_LENGTH: usize = XCHACHA20_IV_LENGTH;
+    const KEY_LENGTH: usize = XCHACHA20_KEY_LENGTH;
+    const CIPHER_ALGORITHM_TYPE: CipherAlgorithmType = CipherAlgorithmType::Stream;
+}
+
+impl CipherAlgorithmTrait for XChaCha20CipherAlgorithm {
+    fn crypt(&mut self, src_data: &[u8], dst_data: &mut [u8]) -> Result<()> {
+        // 这里为了减少拷贝次数，先将src复制到dst中
+        memcpy(dst_data, src_data)?;
+        self.m_algo.apply_keystream(dst_data);
+        Ok(())
+    }
+}
+
+impl SeekableCipherAlgorithmTrait for XChaCha20CipherAlgorithm {
+    fn seek(&mut self, pos: u64) -> Result<()> {
+        self.m_algo
+            .try_seek(pos)
+            .map_err(|e| FAILED_WHEN_SEEKING_STREAM.add_opt_mess(&format!("{e}")))
+    }
+}
+
+impl IVKeyNewTrait for XChaCha20CipherAlgorithm {
+    fn new(iv: &[u8], key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if iv.len() != Self::IV_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        if key.len() != Self::KEY_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+        }
+        let cipher: ExXChaCha20 = ExXChaCha20::new(key.into(), iv.into());
+        Ok(XChaCha20CipherAlgorithm { m_algo: cipher })
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -55,6 +113,8 @@ mod tests {
     use hash::sha512::Sha512Hasher;
 
     use super::*;
+    use crate::crypter::StreamGeneratorTrait;
+    use crate::encoding::base64::Base64Encoding;
     use crate::encoding::hex::HexEncoding;
     use crate::encoding::EncodingTrait;
 
@@ -67,4 +127,53 @@ mod tests {
         println!("plaintext: {plaintext}");
         assert_eq!(plaintext, "123456");
     }
+
+    #[test]
+    fn test_xchacha20() {
+        let string_crypter = StringCrypter::<XChaCha20CipherAlgorithm>::default();
+        let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+        let plaintext = string_crypter.decrypt(&ciphertext, "123456").unwrap();
+        assert_eq!(plaintext, "123456");
+        // 24字节的IV
+        assert_eq!(
+            Base64Encoding::default().decode(&ciphertext).unwrap().len(),
+            XCHACHA20_IV_LENGTH + 4 + 6
+        );
+    }
+
+    #[test]
+    fn test_xchacha20_keystream() {
+        // draft-irtf-cfrg-xchacha-03 附录A.3.2中的测试向量
+        // 其中的密码流从计数器为1的分组开始，因此先跳过第一个分组的64个字节
+        let hexe = HexEncoding::default();
+        let key = hexe
+            .decode("808182838485868788898A8B8C8D8E8F909192939495969798999A9B9C9D9E9F")
+            .unwrap();
+        let iv = hexe
+            .decode("404142434445464748494A4B4C4D4E4F5051525354555658")
+            .unwrap();
+        let mut stream_generator = StreamGenerator::<XChaCha20CipherAlgorithm>::new(
+            &iv,
+            &key,
+            StreamGeneratorMode::Short,
+        )
+        .unwrap();
+        stream_generator.generate(64).unwrap();
+        assert_eq!(
+            concat!(
+                "29624B4B1B140ACE53740E405B2168540FD7D630C1F536FECD722FC3CDDBA7F4",
+                "CCA98CF9E47E5E64D115450F9B125B54449FF76141CA620A1F9CFCAB2A1A8A25",
+                "5E766A5266B878846120EA64AD99AA479471E63BEFCBD37CD1C22A221FE46221",
+                "5CF32C74895BF505863CCDDD48F62916DC6521F1EC50A5AE08903AA259D9BF60",
+                "7CD8026FBA548604F1B6072D91BC91243A5B845F7FD171B02EDC5A0A84CF28DD",
+                "241146BC376E3F48DF5E7FEE1D11048C190A3D3DEB0FEB64B42D9C6FDEEE290F",
+                "A0E6AE2C26C0249EA8C181F7E2FFD100CBE5FD3C4F8271D62B15330CB8FDCF00",
+                "B3DF507CA8C924F7017B7E712D15A2EB5C50484451E54E1B4B995BD8FDD94597",
+                "BB94D7AF0B2C04DF10BA0890899ED9293A0F55B8BAFA999264035F1D4FBE7FE0",
+                "AAFA109A62372027E50E10CDFECCA127",
+            ),
+            hexe.encode(&stream_generator.generate(304).unwrap())
+        );
+        assert!(XChaCha20CipherAlgorithm::new(&iv[..12], &key).is_err());
+    }
 }
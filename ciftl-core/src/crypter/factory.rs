@@ -0,0 +1,268 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::crypter::aead::{
+    AeadBytesCrypter, AeadStringCrypter, Aes256GcmAeadAlgorithm, ChaCha20Poly1305AeadAlgorithm,
+};
+use crate::crypter::aes::{
+    Aes128OfbCipherAlgorithm, Aes256CtrCipherAlgorithm, Aes256OfbCipherAlgorithm,
+};
+use crate::crypter::chacha20::{ChaCha20CipherAlgorithm, XChaCha20CipherAlgorithm};
+use crate::crypter::sm4::Sm4OfbCipherAlgorithm;
+use crate::crypter::{
+    AeadAlgorithmTrait, BytesCrypter, BytesCrypterTrait, CipherAlgorithm, CipherAlgorithmBaseTrait,
+    CipherAlgorithmType, StringCrypter, StringCrypterTrait,
+};
+use crate::hash::sm3::Sm3Hasher;
+use crate::*;
+
+impl CipherAlgorithm {
+    /// 所有的密码算法
+    pub const ALL: [CipherAlgorithm; 8] = [
+        CipherAlgorithm::ChaCha20,
+        CipherAlgorithm::AES256CTR,
+        CipherAlgorithm::AES128OFB,
+        CipherAlgorithm::AES256OFB,
+        CipherAlgorithm::ChaCha20Poly1305,
+        CipherAlgorithm::AES256GCM,
+        CipherAlgorithm::XChaCha20,
+        CipherAlgorithm::SM4OFB,
+    ];
+
+    /// 算法的名称，与命令行工具的-a参数一致
+    pub fn name(&self) -> &'static str {
+        match self {
+            CipherAlgorithm::ChaCha20 => "ChaCha20",
+            CipherAlgorithm::AES256CTR => "AES256CTR",
+            CipherAlgorithm::AES128OFB => "AES128OFB",
+            CipherAlgorithm::AES256OFB => "AES256OFB",
+            CipherAlgorithm::ChaCha20Poly1305 => "ChaCha20Poly1305",
+            CipherAlgorithm::AES256GCM => "AES256GCM",
+            CipherAlgorithm::XChaCha20 => "XChaCha20",
+            CipherAlgorithm::SM4OFB => "SM4",
+        }
+    }
+
+    /// IV的长度，AEAD算法为nonce的长度
+    pub fn iv_length(&self) -> usize {
+        match self {
+            CipherAlgorithm::ChaCha20 => ChaCha20CipherAlgorithm::IV_LENGTH,
+            CipherAlgorithm::AES256CTR => Aes256CtrCipherAlgorithm::IV_LENGTH,
+            CipherAlgorithm::AES128OFB => Aes128OfbCipherAlgorithm::IV_LENGTH,
+            CipherAlgorithm::AES256OFB => Aes256OfbCipherAlgorithm::IV_LENGTH,
+            CipherAlgorithm::ChaCha20Poly1305 => ChaCha20Poly1305AeadAlgorithm::NONCE_LENGTH,
+            CipherAlgorithm::AES256GCM => Aes256GcmAeadAlgorithm::NONCE_LENGTH,
+            CipherAlgorithm::XChaCha20 => XChaCha20CipherAlgorithm::IV_LENGTH,
+            CipherAlgorithm::SM4OFB => Sm4OfbCipherAlgorithm::IV_LENGTH,
+        }
+    }
+
+    /// Key的长度
+    pub fn key_length(&self) -> usize {
+        match self {
+            CipherAlgorithm::ChaCha20 => ChaCha20CipherAlgorithm::KEY_LENGTH,
+            CipherAlgorithm::AES256CTR => Aes256CtrCipherAlgorithm::KEY_LENGTH,
+            CipherAlgorithm::AES128OFB => Aes128OfbCipherAlgorithm::KEY_LENGTH,
+            CipherAlgorithm::AES256OFB => Aes256OfbCipherAlgorithm::KEY_LENGTH,
+            CipherAlgorithm::ChaCha20Poly1305 => {
+                <ChaCha20Poly1305AeadAlgorithm as AeadAlgorithmTrait>::KEY_LENGTH
+            }
+            CipherAlgorithm::AES256GCM => {
+                <Aes256GcmAeadAlgorithm as AeadAlgorithmTrait>::KEY_LENGTH
+            }
+            CipherAlgorithm::XChaCha20 => XChaCha20CipherAlgorithm::KEY_LENGTH,
+            CipherAlgorithm::SM4OFB => Sm4OfbCipherAlgorithm::KEY_LENGTH,
+        }
+    }
+
+    /// 密码算法的类型，AEAD算法的底层都是流密码（或CTR模式）
+    pub fn cipher_algorithm_type(&self) -> CipherAlgorithmType {
+        match self {
+            CipherAlgorithm::ChaCha20 => ChaCha20CipherAlgorithm::CIPHER_ALGORITHM_TYPE,
+            CipherAlgorithm::AES256CTR => Aes256CtrCipherAlgorithm::CIPHER_ALGORITHM_TYPE,
+            CipherAlgorithm::AES128OFB => Aes128OfbCipherAlgorithm::CIPHER_ALGORITHM_TYPE,
+            CipherAlgorithm::AES256OFB => Aes256OfbCipherAlgorithm::CIPHER_ALGORITHM_TYPE,
+            CipherAlgorithm::ChaCha20Poly1305 => CipherAlgorithmType::Stream,
+            CipherAlgorithm::AES256GCM => CipherAlgorithmType::Stream,
+            CipherAlgorithm::XChaCha20 => XChaCha20CipherAlgorithm::CIPHER_ALGORITHM_TYPE,
+            CipherAlgorithm::SM4OFB => Sm4OfbCipherAlgorithm::CIPHER_ALGORITHM_TYPE,
+        }
+    }
+
+    /// 是否为带认证的加密算法
+    pub fn is_aead(&self) -> bool {
+        matches!(
+            self,
+            CipherAlgorithm::ChaCha20Poly1305 | CipherAlgorithm::AES256GCM
+        )
+    }
+
+    /// 创建一个使用默认参数的字符串加密器
+    /// 非AEAD算法使用CRC32C作为校验算法，SM4使用SM3作为校验算法
+    pub fn new_string_crypter(&self) -> Box<dyn StringCrypterTrait> {
+        match self {
+            CipherAlgorithm::ChaCha20 => {
+                Box::new(StringCrypter::<ChaCha20CipherAlgorithm>::default())
+            }
+            CipherAlgorithm::AES256CTR => {
+                Box::new(StringCrypter::<Aes256CtrCipherAlgorithm>::default())
+            }
+            CipherAlgorithm::AES128OFB => {
+                Box::new(StringCrypter::<Aes128OfbCipherAlgorithm>::default())
+            }
+            CipherAlgorithm::AES256OFB => {
+                Box::new(StringCrypter::<Aes256OfbCipherAlgorithm>::default())
+            }
+            CipherAlgorithm::ChaCha20Poly1305 => {
+                Box::new(AeadStringCrypter::<ChaCha20Poly1305AeadAlgorithm>::default())
+            }
+            CipherAlgorithm::AES256GCM => {
+                Box::new(AeadStringCrypter::<Aes256GcmAeadAlgorithm>::default())
+            }
+            CipherAlgorithm::XChaCha20 => {
+                Box::new(StringCrypter::<XChaCha20CipherAlgorithm>::default())
+            }
+            CipherAlgorithm::SM4OFB => {
+                Box::new(StringCrypter::<Sm4OfbCipherAlgorithm, Sm3Hasher>::default())
+            }
+        }
+    }
+
+    /// 创建一个使用默认参数的字节加密器，校验算法的选择与new_string_crypter相同
+    pub fn new_bytes_crypter(&self) -> Box<dyn BytesCrypterTrait> {
+        match self {
+            CipherAlgorithm::ChaCha20 => {
+                Box::new(BytesCrypter::<ChaCha20CipherAlgorithm>::default())
+            }
+            CipherAlgorithm::AES256CTR => {
+                Box::new(BytesCrypter::<Aes256CtrCipherAlgorithm>::default())
+            }
+            CipherAlgorithm::AES128OFB => {
+                Box::new(BytesCrypter::<Aes128OfbCipherAlgorithm>::default())
+            }
+            CipherAlgorithm::AES256OFB => {
+                Box::new(BytesCrypter::<Aes256OfbCipherAlgorithm>::default())
+            }
+            CipherAlgorithm::ChaCha20Poly1305 => {
+                Box::new(AeadBytesCrypter::<ChaCha20Poly1305AeadAlgorithm>::default())
+            }
+            CipherAlgorithm::AES256GCM => {
+                Box::new(AeadBytesCrypter::<Aes256GcmAeadAlgorithm>::default())
+            }
+            CipherAlgorithm::XChaCha20 => {
+                Box::new(BytesCrypter::<XChaCha20CipherAlgorithm>::default())
+            }
+            CipherAlgorithm::SM4OFB => {
+                Box::new(BytesCrypter::<Sm4OfbCipherAlgorithm, Sm3Hasher>::default())
+            }
+        }
+    }
+}
+
+/// 规范化算法名称：转为小写并去掉'-'和'_'，从而"AES-256-GCM"和"aes256gcm"等价
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+impl FromStr for CipherAlgorithm {
+    type Err = CiftlError;
+
+    /// 从名称解析密码算法，忽略大小写以及'-'和'_'，也接受枚举值本身的名称（如"SM4OFB"）
+    fn from_str(s: &str) -> Result<Self> {
+        let name = normalize_name(s);
+        CipherAlgorithm::ALL
+            .iter()
+            .find(|algorithm| {
+                normalize_name(algorithm.name()) == name
+                    || normalize_name(&format!("{algorithm:?}")) == name
+            })
+            .copied()
+            .ok_or_else(|| UNKNOWN_CIPHER_ALGORITHM.add_opt_mess(s))
+    }
+}
+
+impl fmt::Display for CipherAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 根据名称创建一个字符串加密器
+pub fn new_string_crypter_by_name(name: &str) -> Result<Box<dyn StringCrypterTrait>> {
+    Ok(name.parse::<CipherAlgorithm>()?.new_string_crypter())
+}
+
+/// 根据名称创建一个字节加密器
+pub fn new_bytes_crypter_by_name(name: &str) -> Result<Box<dyn BytesCrypterTrait>> {
+    Ok(name.parse::<CipherAlgorithm>()?.new_bytes_crypter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::base64::Base64Encoding;
+    use crate::encoding::EncodingTrait;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn test_cipher_algorithm_metadata() {
+        for algorithm in CipherAlgorithm::ALL {
+            assert_eq!(
+                algorithm.name().parse::<CipherAlgorithm>().unwrap(),
+                algorithm
+            );
+            assert_eq!(
+                CipherAlgorithm::from_u8(algorithm as u8).unwrap(),
+                algorithm
+            );
+        }
+        assert_eq!(CipherAlgorithm::ChaCha20.iv_length(), 12);
+        assert_eq!(CipherAlgorithm::XChaCha20.iv_length(), 24);
+        assert_eq!(CipherAlgorithm::AES128OFB.key_length(), 16);
+        assert_eq!(CipherAlgorithm::AES256GCM.key_length(), 32);
+        assert_eq!(
+            CipherAlgorithm::AES256OFB.cipher_algorithm_type(),
+            CipherAlgorithmType::Block(16)
+        );
+        assert_eq!(
+            CipherAlgorithm::AES256CTR.cipher_algorithm_type(),
+            CipherAlgorithmType::Stream
+        );
+        assert!(CipherAlgorithm::ChaCha20Poly1305.is_aead());
+        assert!(!CipherAlgorithm::SM4OFB.is_aead());
+        assert_eq!(
+            "sm4ofb".parse::<CipherAlgorithm>().unwrap(),
+            CipherAlgorithm::SM4OFB
+        );
+        assert_eq!(
+            "AES-256-GCM".parse::<CipherAlgorithm>().unwrap(),
+            CipherAlgorithm::AES256GCM
+        );
+        let res = "DES".parse::<CipherAlgorithm>();
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+    }
+
+    #[test]
+    fn test_new_crypter_by_name() {
+        for algorithm in CipherAlgorithm::ALL {
+            let string_crypter = new_string_crypter_by_name(algorithm.name()).unwrap();
+            let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+            assert_eq!(
+                string_crypter.decrypt(&ciphertext, "123456").unwrap(),
+                "123456"
+            );
+            // 字符串加密器的结果解码后可以由字节加密器解密
+            let bytes_crypter = algorithm.new_bytes_crypter();
+            let cipher_data = Base64Encoding::default().decode(&ciphertext).unwrap();
+            assert_eq!(
+                bytes_crypter.decrypt(&cipher_data, "123456").unwrap(),
+                b"123456".to_vec()
+            );
+        }
+        assert!(new_bytes_crypter_by_name("ChaCha21").is_err());
+    }
+}
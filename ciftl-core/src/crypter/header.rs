@@ -0,0 +1,717 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::crypter::aead::{
+    AeadBytesCrypter, Aes256GcmAeadAlgorithm, ChaCha20Poly1305AeadAlgorithm,
+};
+use crate::crypter::aes::{
+    Aes128OfbCipherAlgorithm, Aes256CtrCipherAlgorithm, Aes256OfbCipherAlgorithm,
+};
+use crate::crypter::chacha20::{ChaCha20CipherAlgorithm, XChaCha20CipherAlgorithm};
+use crate::crypter::kdf::{
+    Argon2idKdf, LegacySha256Kdf, Pbkdf2HmacSha256Kdf, ScryptKdf, ARGON2_MAX_M_COST,
+    ARGON2_MAX_P_COST, ARGON2_MAX_T_COST, PBKDF2_MAX_ITERATIONS, SCRYPT_MAX_LOG_N,
+    SCRYPT_MAX_MEMORY, SCRYPT_MAX_P, SCRYPT_MAX_R,
+};
+use crate::crypter::sm4::Sm4OfbCipherAlgorithm;
+use crate::crypter::{
+    decrypt_string, encrypt_string, AeadAlgorithmTrait, BytesCrypter, BytesCrypterTrait,
+    CipherAlgorithm, CipherAlgorithmTrait, IVKeyNewTrait, KeyNewTrait, StringCrypterTrait,
+};
+use crate::encoding::base64::Base64Encoding;
+use crate::encoding::EncodingTrait as _;
+use crate::hash::blake2::{Blake2b512Hasher, Blake2s256Hasher};
+use crate::hash::blake3::Blake3Hasher;
+#[cfg(feature = "md5")]
+use crate::hash::md5::Md5Hasher;
+use crate::hash::ripemd160::Ripemd160Hasher;
+use crate::hash::sha1::Sha1Hasher;
+use crate::hash::sha224::Sha224Hasher;
+use crate::hash::sha256::Sha256Hasher;
+use crate::hash::sha3::{
+    Keccak256Hasher, Sha3_224Hasher, Sha3_256Hasher, Sha3_384Hasher, Sha3_512Hasher,
+    Shake128Hasher, Shake256Hasher,
+};
+use crate::hash::sha384::Sha384Hasher;
+use crate::hash::sha512::Sha512Hasher;
+use crate::hash::sm3::Sm3Hasher;
+use crate::hash::{HashAlgorithm, HasherTrait};
+use crate::*;
+
+/// 密文头部的魔数
+pub const CIPHERTEXT_MAGIC: &[u8; 4] = b"CFTL";
+/// 当前的密文头部版本
+pub const CIPHERTEXT_VERSION: u8 = 1;
+/// 校验算法标识为0时表示由AEAD的认证标签负责校验
+const AEAD_TAG_CHECKSUM_IDENTIFIER: u8 = 0;
+
+/// 所有支持的密钥派生算法，其值同时作为密文头部中的算法标识，不应修改
+#[derive(FromPrimitive, Clone, Copy, PartialEq, Debug)]
+pub enum KdfAlgorithm {
+    LegacySha256 = 1,
+    Pbkdf2HmacSha256 = 2,
+    Scrypt = 3,
+    Argon2id = 4,
+}
+
+/// 密钥派生算法及其参数
+#[derive(Clone, PartialEq, Debug)]
+pub enum KdfParameters {
+    LegacySha256,
+    Pbkdf2HmacSha256 {
+        iterations: u32,
+    },
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+    },
+    Argon2id {
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+    },
+}
+
+impl KdfParameters {
+    pub fn algorithm(&self) -> KdfAlgorithm {
+        match self {
+            KdfParameters::LegacySha256 => KdfAlgorithm::LegacySha256,
+            KdfParameters::Pbkdf2HmacSha256 { .. } => KdfAlgorithm::Pbkdf2HmacSha256,
+            KdfParameters::Scrypt { .. } => KdfAlgorithm::Scrypt,
+            KdfParameters::Argon2id { .. } => KdfAlgorithm::Argon2id,
+        }
+    }
+
+    /// 检查参数是否超出允许的上限，解析密文头部时在派生密钥之前调用
+    pub fn check_limits(&self) -> Result<()> {
+        let exceeded = |name: &str, value: u64, max: u64| -> Result<()> {
+            if value > max {
+                return Err(INVALID_KDF_PARAMETERS
+                    .add_opt_mess(&format!("{name}为{value}，超过了上限{max}")));
+            }
+            Ok(())
+        };
+        match *self {
+            KdfParameters::LegacySha256 => {}
+            KdfParameters::Pbkdf2HmacSha256 { iterations } => {
+                exceeded("迭代次数", iterations as u64, PBKDF2_MAX_ITERATIONS as u64)?;
+            }
+            KdfParameters::Scrypt { log_n, r, p } => {
+                exceeded("log_n", log_n as u64, SCRYPT_MAX_LOG_N as u64)?;
+                exceeded("r", r as u64, SCRYPT_MAX_R as u64)?;
+                exceeded("p", p as u64, SCRYPT_MAX_P as u64)?;
+                exceeded("内存开销", 128 * r as u64 * (1 << log_n), SCRYPT_MAX_MEMORY)?;
+            }
+            KdfParameters::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                exceeded("m_cost", m_cost as u64, ARGON2_MAX_M_COST as u64)?;
+                exceeded("t_cost", t_cost as u64, ARGON2_MAX_T_COST as u64)?;
+                exceeded("p_cost", p_cost as u64, ARGON2_MAX_P_COST as u64)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for KdfParameters {
+    /// 默认使用Argon2id的默认参数
+    fn default() -> Self {
+        let kdf = Argon2idKdf::default();
+        KdfParameters::Argon2id {
+            m_cost: kdf.m_cost(),
+            t_cost: kdf.t_cost(),
+            p_cost: kdf.p_cost(),
+        }
+    }
+}
+
+/// 密文头部，记录了解密所需的全部算法信息
+/// 格式为：魔数(4) || 版本(1) || 密码算法(1) || 校验算法(1) || 密钥派生算法(1) || 密钥派生参数
+/// 其中整数参数均按大端序存储
+#[derive(Clone, PartialEq, Debug)]
+pub struct CiphertextHeader {
+    /// 密码算法
+    pub cipher: CipherAlgorithm,
+    /// 校验算法，必须是密码学哈希算法，使用AEAD时为None
+    pub checksum: Option<HashAlgorithm>,
+    /// 密钥派生算法及其参数
+    pub kdf: KdfParameters,
+}
+
+impl CiphertextHeader {
+    /// 序列化头部
+    pub fn to_bytes(&self) -> ByteVector {
+        let mut res = CIPHERTEXT_MAGIC.to_vec();
+        res.push(CIPHERTEXT_VERSION);
+        res.push(self.cipher as u8);
+        res.push(
+            self.checksum
+                .map_or(AEAD_TAG_CHECKSUM_IDENTIFIER, |c| c as u8),
+        );
+        res.push(self.kdf.algorithm() as u8);
+        match self.kdf {
+            KdfParameters::LegacySha256 => {}
+            KdfParameters::Pbkdf2HmacSha256 { iterations } => {
+                res.extend(iterations.to_be_bytes());
+            }
+            KdfParameters::Scrypt { log_n, r, p } => {
+                res.push(log_n);
+                res.extend(r.to_be_bytes());
+                res.extend(p.to_be_bytes());
+            }
+            KdfParameters::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                res.extend(m_cost.to_be_bytes());
+                res.extend(t_cost.to_be_bytes());
+                res.extend(p_cost.to_be_bytes());
+            }
+        }
+        res
+    }
+
+    /// 从数据中解析头部，返回头部和剩余的内容
+    /// 密钥派生参数超出上限时返回INVALID_KDF_PARAMETERS
+    /// 校验算法不是密码学哈希算法时返回INCOMPATIBLE_ALGORITHM_COMBINATION
+    pub fn from_bytes(data: &[u8]) -> Result<(CiphertextHeader, ByteVector)> {
+        let mut mt = MemoryTaker::new(data);
+        let mut magic = [0u8; 4];
+        let mut fields = [0u8; 4];
+        mt.take(&mut magic)
+            .map_err(|_| BAD_CIPHERTEXT_MAGIC.clone())?;
+        if &magic != CIPHERTEXT_MAGIC {
+            return Err(BAD_CIPHERTEXT_MAGIC.clone());
+        }
+        mt.take(&mut fields)?;
+        let [version, cipher, checksum, kdf] = fields;
+        if version != CIPHERTEXT_VERSION {
+            return Err(UNSUPPORTED_CIPHERTEXT_VERSION.add_opt_mess(&format!("{version}")));
+        }
+        let cipher = CipherAlgorithm::from_u8(cipher)
+            .ok_or(UNKNOWN_ALGORITHM_IDENTIFIER.add_opt_mess(&format!("密码算法：{cipher}")))?;
+        let checksum = match checksum {
+            AEAD_TAG_CHECKSUM_IDENTIFIER => None,
+            c => Some(
+                HashAlgorithm::from_u8(c)
+                    .ok_or(UNKNOWN_ALGORITHM_IDENTIFIER.add_opt_mess(&format!("校验算法：{c}")))?,
+            ),
+        };
+        if let Some(c) = checksum.filter(|c| !c.is_cryptographic()) {
+            return Err(non_cryptographic_checksum(c));
+        }
+        let kdf_algorithm = KdfAlgorithm::from_u8(kdf)
+            .ok_or(UNKNOWN_ALGORITHM_IDENTIFIER.add_opt_mess(&format!("密钥派生算法：{kdf}")))?;
+        let take_u32 = |mt: &mut MemoryTaker| -> Result<u32> {
+            let mut buffer = [0u8; 4];
+            mt.take(&mut buffer)?;
+            Ok(u32::from_be_bytes(buffer))
+        };
+        let kdf = match kdf_algorithm {
+            KdfAlgorithm::LegacySha256 => KdfParameters::LegacySha256,
+            KdfAlgorithm::Pbkdf2HmacSha256 => KdfParameters::Pbkdf2HmacSha256 {
+                iterations: take_u32(&mut mt)?,
+            },
+            KdfAlgorithm::Scrypt => {
+                let mut log_n = [0u8; 1];
+                mt.take(&mut log_n)?;
+                KdfParameters::Scrypt {
+                    log_n: log_n[0],
+                    r: take_u32(&mut mt)?,
+                    p: take_u32(&mut mt)?,
+                }
+            }
+            KdfAlgorithm::Argon2id => KdfParameters::Argon2id {
+                m_cost: take_u32(&mut mt)?,
+                t_cost: take_u32(&mut mt)?,
+                p_cost: take_u32(&mut mt)?,
+            },
+        };
+        // 头部来自不可信的数据，在派生密钥之前拒绝开销过大的参数
+        kdf.check_limits()?;
+        let body = mt.take_all()?;
+        Ok((
+            CiphertextHeader {
+                cipher,
+                checksum,
+                kdf,
+            },
+            body,
+        ))
+    }
+}
+
+impl Default for CiphertextHeader {
+    /// 默认使用ChaCha20Poly1305和Argon2id
+    fn default() -> Self {
+        CiphertextHeader {
+            cipher: CipherAlgorithm::ChaCha20Poly1305,
+            checksum: None,
+            kdf: KdfParameters::default(),
+        }
+    }
+}
+
+/// 根据头部创建对应的字节加密器
+fn build_crypter(header: &CiphertextHeader) -> Result<Box<dyn BytesCrypterTrait>> {
+    match header.cipher {
+        CipherAlgorithm::ChaCha20 => build_stream_crypter::<ChaCha20CipherAlgorithm>(header),
+        CipherAlgorithm::AES256CTR => build_stream_crypter::<Aes256CtrCipherAlgorithm>(header),
+        CipherAlgorithm::AES128OFB => build_stream_crypter::<Aes128OfbCipherAlgorithm>(header),
+        CipherAlgorithm::AES256OFB => build_stream_crypter::<Aes256OfbCipherAlgorithm>(header),
+        CipherAlgorithm::ChaCha20Poly1305 => {
+            build_aead_crypter::<ChaCha20Poly1305AeadAlgorithm>(header)
+        }
+        CipherAlgorithm::AES256GCM => build_aead_crypter::<Aes256GcmAeadAlgorithm>(header),
+        CipherAlgorithm::XChaCha20 => build_stream_crypter::<XChaCha20CipherAlgorithm>(header),
+        CipherAlgorithm::SM4OFB => build_stream_crypter::<Sm4OfbCipherAlgorithm>(header),
+    }
+}
+
+/// 非密码学哈希算法是线性的或者很容易构造碰撞，不能作为密文的校验算法
+fn non_cryptographic_checksum(checksum: HashAlgorithm) -> CiftlError {
+    INCOMPATIBLE_ALGORITHM_COMBINATION.add_opt_mess(&format!(
+        "{:?}不是密码学哈希算法，不能作为校验算法",
+        checksum
+    ))
+}
+
+fn build_stream_crypter<CA: CipherAlgorithmTrait + IVKeyNewTrait + 'static>(
+    header: &CiphertextHeader,
+) -> Result<Box<dyn BytesCrypterTrait>> {
+    let checksum = header
+        .checksum
+        .ok_or(INCOMPATIBLE_ALGORITHM_COMBINATION.add_opt_mess("非AEAD密码算法必须指定校验算法"))?;
+    match checksum {
+        HashAlgorithm::Sha1 => build_stream_crypter_with_hasher::<CA, Sha1Hasher>(&header.kdf),
+        HashAlgorithm::Sha256 => build_stream_crypter_with_hasher::<CA, Sha256Hasher>(&header.kdf),
+        HashAlgorithm::Sha512 => build_stream_crypter_with_hasher::<CA, Sha512Hasher>(&header.kdf),
+        HashAlgorithm::Sha3_224 => {
+            build_stream_crypter_with_hasher::<CA, Sha3_224Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Sha3_256 => {
+            build_stream_crypter_with_hasher::<CA, Sha3_256Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Sha3_384 => {
+            build_stream_crypter_with_hasher::<CA, Sha3_384Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Sha3_512 => {
+            build_stream_crypter_with_hasher::<CA, Sha3_512Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Keccak256 => {
+            build_stream_crypter_with_hasher::<CA, Keccak256Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Shake128 => {
+            build_stream_crypter_with_hasher::<CA, Shake128Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Shake256 => {
+            build_stream_crypter_with_hasher::<CA, Shake256Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Blake2b512 => {
+            build_stream_crypter_with_hasher::<CA, Blake2b512Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Blake2s256 => {
+            build_stream_crypter_with_hasher::<CA, Blake2s256Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Blake3 => build_stream_crypter_with_hasher::<CA, Blake3Hasher>(&header.kdf),
+        HashAlgorithm::Sm3 => build_stream_crypter_with_hasher::<CA, Sm3Hasher>(&header.kdf),
+        #[cfg(feature = "md5")]
+        HashAlgorithm::Md5 => build_stream_crypter_with_hasher::<CA, Md5Hasher>(&header.kdf),
+        #[cfg(not(feature = "md5"))]
+        HashAlgorithm::Md5 => {
+            Err(HASH_ALGORITHM_NOT_ENABLED.add_opt_mess("MD5需要启用md5特性"))
+        }
+        HashAlgorithm::Sha224 => build_stream_crypter_with_hasher::<CA, Sha224Hasher>(&header.kdf),
+        HashAlgorithm::Sha384 => build_stream_crypter_with_hasher::<CA, Sha384Hasher>(&header.kdf),
+        HashAlgorithm::Ripemd160 => {
+            build_stream_crypter_with_hasher::<CA, Ripemd160Hasher>(&header.kdf)
+        }
+        HashAlgorithm::Crc32
+        | HashAlgorithm::Crc32c
+        | HashAlgorithm::Crc16Ccitt
+        | HashAlgorithm::Crc16CcittFalse
+        | HashAlgorithm::Crc16Modbus
+        | HashAlgorithm::Crc32Bzip2
+        | HashAlgorithm::Crc64Ecma
+        | HashAlgorithm::Crc64Xz
+        | HashAlgorithm::Xxh32
+        | HashAlgorithm::Xxh64
+        | HashAlgorithm::Xxh3
+        | HashAlgorithm::Adler32
+        | HashAlgorithm::Fnv1a32
+        | HashAlgorithm::Fnv1a64
+        | HashAlgorithm::Murmur3_32
+        | HashAlgorithm::Murmur3_128 => Err(non_cryptographic_checksum(checksum)),
+    }
+}
+
+fn build_stream_crypter_with_hasher<
+    CA: CipherAlgorithmTrait + IVKeyNewTrait + 'static,
+    HR: HasherTrait + Default + 'static,
+>(
+    kdf: &KdfParameters,
+) -> Result<Box<dyn BytesCrypterTrait>> {
+    Ok(match *kdf {
+        KdfParameters::LegacySha256 => Box::new(BytesCrypter::<CA, HR, LegacySha256Kdf>::default()),
+        KdfParameters::Pbkdf2HmacSha256 { iterations } => Box::new(
+            BytesCrypter::<CA, HR, _>::with_kdf(Pbkdf2HmacSha256Kdf::new(iterations)?),
+        ),
+        KdfParameters::Scrypt { log_n, r, p } => Box::new(BytesCrypter::<CA, HR, _>::with_kdf(
+            ScryptKdf::new(log_n, r, p)?,
+        )),
+        KdfParameters::Argon2id {
+            m_cost,
+            t_cost,
+            p_cost,
+        } => Box::new(BytesCrypter::<CA, HR, _>::with_kdf(Argon2idKdf::new(
+            m_cost, t_cost, p_cost,
+        )?)),
+    })
+}
+
+fn build_aead_crypter<AA: AeadAlgorithmTrait + KeyNewTrait + 'static>(
+    header: &CiphertextHeader,
+) -> Result<Box<dyn BytesCrypterTrait>> {
+    if header.checksum.is_some() {
+        return Err(INCOMPATIBLE_ALGORITHM_COMBINATION
+            .add_opt_mess("AEAD密码算法由认证标签校验，不能再指定校验算法"));
+    }
+    Ok(match header.kdf {
+        KdfParameters::LegacySha256 => Box::new(AeadBytesCrypter::<AA, LegacySha256Kdf>::default()),
+        KdfParameters::Pbkdf2HmacSha256 { iterations } => Box::new(
+            AeadBytesCrypter::<AA, _>::with_kdf(Pbkdf2HmacSha256Kdf::new(iterations)?),
+        ),
+        KdfParameters::Scrypt { log_n, r, p } => Box::new(AeadBytesCrypter::<AA, _>::with_kdf(
+            ScryptKdf::new(log_n, r, p)?,
+        )),
+        KdfParameters::Argon2id {
+            m_cost,
+            t_cost,
+            p_cost,
+        } => Box::new(AeadBytesCrypter::<AA, _>::with_kdf(Argon2idKdf::new(
+            m_cost, t_cost, p_cost,
+        )?)),
+    })
+}
+
+/// VersionedBytesCrypter在密文前附加一个自描述的头部
+/// 加密时使用创建时指定的算法，解密时根据密文的头部自动选择对应的算法
+pub struct VersionedBytesCrypter {
+    m_header: CiphertextHeader,
+    m_crypter: Box<dyn BytesCrypterTrait>,
+}
+
+impl VersionedBytesCrypter {
+    /// 创建一个加密时使用header中的算法的字节加密器
+    pub fn new(header: CiphertextHeader) -> Result<Self> {
+        let crypter = build_crypter(&header)?;
+        Ok(VersionedBytesCrypter {
+            m_header: header,
+            m_crypter: crypter,
+        })
+    }
+
+    /// 加密时使用的头部
+    pub fn header(&self) -> &CiphertextHeader {
+        &self.m_header
+    }
+
+    /// 读取密文的头部而不进行解密
+    pub fn read_header(data: &[u8]) -> Result<CiphertextHeader> {
+        let (header, _) = CiphertextHeader::from_bytes(data)?;
+        Ok(header)
+    }
+}
+
+impl Default for VersionedBytesCrypter {
+    fn default() -> Self {
+        let header = CiphertextHeader::default();
+        let crypter = build_crypter(&header).unwrap();
+        VersionedBytesCrypter {
+            m_header: header,
+            m_crypter: crypter,
+        }
+    }
+}
+
+impl BytesCrypterTrait for VersionedBytesCrypter {
+    fn encrypt(&self, data: &[u8], password: &str) -> Result<ByteVector> {
+        let body = self.m_crypter.encrypt(data, password)?;
+        Ok([&self.m_header.to_bytes()[..], &body[..]].concat())
+    }
+
+    fn decrypt(&self, data: &[u8], password: &str) -> Result<ByteVector> {
+        let (header, body) = CiphertextHeader::from_bytes(data)?;
+        // 根据头部选择解密所用的算法
+        let crypter = build_crypter(&header)?;
+        crypter.decrypt(&body, password)
+    }
+}
+
+/// VersionedStringCrypter是VersionedBytesCrypter的一层包装，输出为其结果的Base64编码
+#[derive(Default)]
+pub struct VersionedStringCrypter {
+    m_bytes_crypter: VersionedBytesCrypter,
+}
+
+impl VersionedStringCrypter {
+    /// 创建一个加密时使用header中的算法的文本加密器
+    pub fn new(header: CiphertextHeader) -> Result<Self> {
+        Ok(VersionedStringCrypter {
+            m_bytes_crypter: VersionedBytesCrypter::new(header)?,
+        })
+    }
+
+    /// 加密时使用的头部
+    pub fn header(&self) -> &CiphertextHeader {
+        self.m_bytes_crypter.header()
+    }
+
+    /// 读取密文的头部而不进行解密
+    pub fn read_header(data: &str) -> Result<CiphertextHeader> {
+        let data = Base64Encoding::default().decode(data)?;
+        VersionedBytesCrypter::read_header(&data)
+    }
+}
+
+impl StringCrypterTrait for VersionedStringCrypter {
+    fn encrypt(&self, data: &str, password: &str) -> Result<String> {
+        encrypt_string(&self.m_bytes_crypter, data, password)
+    }
+
+    fn decrypt(&self, data: &str, password: &str) -> Result<String> {
+        decrypt_string(&self.m_bytes_crypter, data, password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cheap_kdfs() -> Vec<KdfParameters> {
+        vec![
+            KdfParameters::LegacySha256,
+            KdfParameters::Pbkdf2HmacSha256 { iterations: 1000 },
+            KdfParameters::Scrypt {
+                log_n: 8,
+                r: 8,
+                p: 1,
+            },
+            KdfParameters::Argon2id {
+                m_cost: 64,
+                t_cost: 1,
+                p_cost: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_header_serialization() {
+        for kdf in cheap_kdfs() {
+            let header = CiphertextHeader {
+                cipher: CipherAlgorithm::AES256CTR,
+                checksum: Some(HashAlgorithm::Sha256),
+                kdf,
+            };
+            let bytes = [&header.to_bytes()[..], b"body"].concat();
+            assert_eq!(&bytes[..4], CIPHERTEXT_MAGIC);
+            let (parsed, body) = CiphertextHeader::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, header);
+            assert_eq!(body, b"body".to_vec());
+        }
+        let header = CiphertextHeader::default().to_bytes();
+        // 错误的魔数
+        let mut bad = header.clone();
+        bad[0] = b'X';
+        assert!(CiphertextHeader::from_bytes(&bad).is_err());
+        // 不支持的版本
+        let mut bad = header.clone();
+        bad[4] = 99;
+        assert!(CiphertextHeader::from_bytes(&bad).is_err());
+        // 未知的密码算法
+        let mut bad = header.clone();
+        bad[5] = 200;
+        let res = CiphertextHeader::from_bytes(&bad);
+        println!("错误：{}", res.unwrap_err());
+        // 截断的参数
+        assert!(CiphertextHeader::from_bytes(&header[..header.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_versioned_string_crypter() {
+        let ciphers = [
+            (CipherAlgorithm::AES256CTR, Some(HashAlgorithm::Sha256)),
+            (CipherAlgorithm::AES256OFB, Some(HashAlgorithm::Sha512)),
+            (CipherAlgorithm::ChaCha20Poly1305, None),
+            (CipherAlgorithm::AES256GCM, None),
+            (CipherAlgorithm::XChaCha20, Some(HashAlgorithm::Sha1)),
+            (CipherAlgorithm::ChaCha20, Some(HashAlgorithm::Sha3_256)),
+            (CipherAlgorithm::AES256CTR, Some(HashAlgorithm::Shake128)),
+            (CipherAlgorithm::AES256OFB, Some(HashAlgorithm::Blake2b512)),
+            (CipherAlgorithm::XChaCha20, Some(HashAlgorithm::Blake3)),
+            (CipherAlgorithm::SM4OFB, Some(HashAlgorithm::Sm3)),
+            (CipherAlgorithm::AES128OFB, Some(HashAlgorithm::Sha224)),
+            (CipherAlgorithm::ChaCha20, Some(HashAlgorithm::Sha384)),
+            (CipherAlgorithm::AES256CTR, Some(HashAlgorithm::Ripemd160)),
+        ];
+        // 用于解密的实例使用默认的算法，它需要从头部中识别出加密时的算法
+        let decrypter = VersionedStringCrypter::default();
+        for (cipher, checksum) in ciphers {
+            for kdf in cheap_kdfs() {
+                let header = CiphertextHeader {
+                    cipher,
+                    checksum,
+                    kdf,
+                };
+                let crypter = VersionedStringCrypter::new(header.clone()).unwrap();
+                let ciphertext = crypter.encrypt("123456", "123456").unwrap();
+                assert_eq!(
+                    VersionedStringCrypter::read_header(&ciphertext).unwrap(),
+                    header
+                );
+                assert_eq!(decrypter.decrypt(&ciphertext, "123456").unwrap(), "123456");
+                assert!(decrypter.decrypt(&ciphertext, "654321").is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_md5_checksum() {
+        let header = CiphertextHeader {
+            cipher: CipherAlgorithm::ChaCha20,
+            checksum: Some(HashAlgorithm::Md5),
+            kdf: KdfParameters::LegacySha256,
+        };
+        let res = VersionedStringCrypter::new(header);
+        // 未启用md5特性时无法使用MD5作为校验算法
+        if cfg!(feature = "md5") {
+            let crypter = res.unwrap();
+            let ciphertext = crypter.encrypt("123456", "123456").unwrap();
+            assert_eq!(crypter.decrypt(&ciphertext, "123456").unwrap(), "123456");
+        } else {
+            let err = format!("{}", res.err().unwrap());
+            println!("错误：{}", err);
+            assert!(err.starts_with(&format!("{}", HASH_ALGORITHM_NOT_ENABLED)));
+        }
+    }
+
+    #[test]
+    fn test_versioned_bytes_crypter() {
+        let crypter = VersionedBytesCrypter::new(CiphertextHeader {
+            cipher: CipherAlgorithm::AES256CTR,
+            checksum: Some(HashAlgorithm::Sha256),
+            kdf: KdfParameters::LegacySha256,
+        })
+        .unwrap();
+        let decrypter = VersionedBytesCrypter::default();
+        for plain_data in [vec![0xFF, 0xFE, 0x00, 0x80, 0xC3], vec![]] {
+            let cipher_data = crypter.encrypt(&plain_data, "123456").unwrap();
+            assert_eq!(
+                &VersionedBytesCrypter::read_header(&cipher_data).unwrap(),
+                crypter.header()
+            );
+            assert_eq!(
+                decrypter.decrypt(&cipher_data, "123456").unwrap(),
+                plain_data
+            );
+        }
+    }
+
+    #[test]
+    fn test_incompatible_combination() {
+        assert!(VersionedStringCrypter::new(CiphertextHeader {
+            cipher: CipherAlgorithm::ChaCha20,
+            checksum: None,
+            kdf: KdfParameters::LegacySha256,
+        })
+        .is_err());
+        assert!(VersionedStringCrypter::new(CiphertextHeader {
+            cipher: CipherAlgorithm::AES256GCM,
+            checksum: Some(HashAlgorithm::Crc32c),
+            kdf: KdfParameters::LegacySha256,
+        })
+        .is_err());
+        // CRC和快速哈希算法不能作为校验算法，创建加密器和解析头部时都会被拒绝
+        for checksum in [
+            HashAlgorithm::Crc32,
+            HashAlgorithm::Crc16Modbus,
+            HashAlgorithm::Crc64Xz,
+            HashAlgorithm::Xxh3,
+            HashAlgorithm::Adler32,
+            HashAlgorithm::Murmur3_128,
+        ] {
+            let header = CiphertextHeader {
+                cipher: CipherAlgorithm::ChaCha20,
+                checksum: Some(checksum),
+                kdf: KdfParameters::LegacySha256,
+            };
+            let res = VersionedStringCrypter::new(header.clone());
+            let err = format!("{}", res.err().unwrap());
+            println!("错误：{}", err);
+            assert!(err.starts_with(&format!("{}", INCOMPATIBLE_ALGORITHM_COMBINATION)));
+            let data = [&header.to_bytes()[..], &[0x00; 64]].concat();
+            let err = format!("{}", CiphertextHeader::from_bytes(&data).unwrap_err());
+            assert!(err.starts_with(&format!("{}", INCOMPATIBLE_ALGORITHM_COMBINATION)));
+        }
+        assert!(VersionedStringCrypter::new(CiphertextHeader {
+            cipher: CipherAlgorithm::AES256GCM,
+            checksum: None,
+            kdf: KdfParameters::Pbkdf2HmacSha256 { iterations: 0 },
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_oversized_kdf_parameters() {
+        let oversized = [
+            KdfParameters::Pbkdf2HmacSha256 {
+                iterations: u32::MAX,
+            },
+            KdfParameters::Scrypt {
+                log_n: 40,
+                r: 8,
+                p: 1,
+            },
+            // 每个参数都不超过上限，但内存开销超过上限
+            KdfParameters::Scrypt {
+                log_n: SCRYPT_MAX_LOG_N,
+                r: SCRYPT_MAX_R,
+                p: 1,
+            },
+            KdfParameters::Argon2id {
+                m_cost: u32::MAX,
+                t_cost: u32::MAX,
+                p_cost: 1,
+            },
+            KdfParameters::Argon2id {
+                m_cost: 64,
+                t_cost: u32::MAX,
+                p_cost: 1,
+            },
+        ];
+        let decrypter = VersionedStringCrypter::default();
+        for kdf in oversized {
+            let header = CiphertextHeader {
+                cipher: CipherAlgorithm::ChaCha20Poly1305,
+                checksum: None,
+                kdf,
+            };
+            let data = [&header.to_bytes()[..], &[0x00; 64]].concat();
+            let res = decrypter.decrypt(&Base64Encoding::default().encode(&data), "123456");
+            let err = format!("{}", res.unwrap_err());
+            assert!(err.starts_with(&format!("{}", INVALID_KDF_PARAMETERS)));
+            println!("错误：{}", err);
+            assert!(CiphertextHeader::from_bytes(&data).is_err());
+        }
+        // 不超过上限的参数可以正常解析
+        for kdf in cheap_kdfs() {
+            assert!(kdf.check_limits().is_ok());
+        }
+        assert!(KdfParameters::default().check_limits().is_ok());
+    }
+}
@@ -0,0 +1,332 @@
+use std::num::NonZeroU32;
+
+use ::argon2::Version as ExArgon2Version;
+use ::argon2::{Algorithm as ExArgon2Algorithm, Argon2 as ExArgon2, Params as ExArgon2Params};
+use ::scrypt::{scrypt as ex_scrypt, Params as ExScryptParams};
+use ring::pbkdf2;
+
+use crate::crypter::{generate_key_from_password, KeyDerivationTrait};
+use crate::hash::sha256::Sha256Hasher;
+use crate::*;
+
+/// 带盐的密钥派生算法所使用的盐的长度
+pub const KDF_SALT_LENGTH: usize = 16;
+
+/// 从密文头部中读取参数时允许的上限，约为默认值的十几倍
+/// 头部来自不可信的密文，不加限制时构造的头部可以让解密占用巨量的内存或时间
+/// PBKDF2的最大迭代次数
+pub const PBKDF2_MAX_ITERATIONS: u32 = 10_000_000;
+/// scrypt的N的以2为底的对数的最大值
+pub const SCRYPT_MAX_LOG_N: u8 = 20;
+/// scrypt的块大小参数r的最大值
+pub const SCRYPT_MAX_R: u32 = 32;
+/// scrypt的并行参数p的最大值
+pub const SCRYPT_MAX_P: u32 = 4;
+/// scrypt的最大内存开销（128 * r * N），单位为字节
+pub const SCRYPT_MAX_MEMORY: u64 = 1024 * 1024 * 1024;
+/// Argon2id的最大内存开销，单位为KiB
+pub const ARGON2_MAX_M_COST: u32 = 1024 * 1024;
+/// Argon2id的最大迭代次数
+pub const ARGON2_MAX_T_COST: u32 = 16;
+/// Argon2id的最大并行度
+pub const ARGON2_MAX_P_COST: u32 = 16;
+
+/// 旧版本的密钥派生方式，即sha256及其链式再哈希，不使用盐
+/// 为了兼容已有的密文，StringCrypter默认使用该方式
+#[derive(Clone, Default)]
+pub struct LegacySha256Kdf;
+
+impl KeyDerivationTrait for LegacySha256Kdf {
+    const SALT_LENGTH: usize = 0;
+
+    fn derive_key(&self, password: &str, _salt: &[u8], n: usize) -> Result<ByteVector> {
+        Ok(generate_key_from_password::<Sha256Hasher>(password, n))
+    }
+}
+
+/// PBKDF2-HMAC-SHA256
+#[derive(Clone)]
+pub struct Pbkdf2HmacSha256Kdf {
+    /// 迭代次数
+    m_iterations: u32,
+}
+
+impl Pbkdf2HmacSha256Kdf {
+    pub fn new(iterations: u32) -> Result<Self> {
+        if iterations == 0 {
+            return Err(INVALID_KDF_PARAMETERS.add_opt_mess("迭代次数不能为0"));
+        }
+        Ok(Pbkdf2HmacSha256Kdf {
+            m_iterations: iterations,
+        })
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.m_iterations
+    }
+}
+
+impl Default for Pbkdf2HmacSha256Kdf {
+    /// 默认迭代600000次
+    fn default() -> Self {
+        Pbkdf2HmacSha256Kdf {
+            m_iterations: 600_000,
+        }
+    }
+}
+
+impl KeyDerivationTrait for Pbkdf2HmacSha256Kdf {
+    const SALT_LENGTH: usize = KDF_SALT_LENGTH;
+
+    fn derive_key(&self, password: &str, salt: &[u8], n: usize) -> Result<ByteVector> {
+        let iterations = NonZeroU32::new(self.m_iterations)
+            .ok_or(INVALID_KDF_PARAMETERS.add_opt_mess("迭代次数不能为0"))?;
+        let mut res = vec![0x00; n];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            salt,
+            password.as_bytes(),
+            &mut res,
+        );
+        Ok(res)
+    }
+}
+
+/// scrypt
+#[derive(Clone)]
+pub struct ScryptKdf {
+    /// CPU/内存开销参数N的以2为底的对数
+    m_log_n: u8,
+    /// 块大小参数r
+    m_r: u32,
+    /// 并行参数p
+    m_p: u32,
+}
+
+impl ScryptKdf {
+    pub fn new(log_n: u8, r: u32, p: u32) -> Result<Self> {
+        ExScryptParams::new(log_n, r, p, ExScryptParams::RECOMMENDED_LEN)
+            .map_err(|e| INVALID_KDF_PARAMETERS.add_opt_mess(&format!("{e}")))?;
+        Ok(ScryptKdf {
+            m_log_n: log_n,
+            m_r: r,
+            m_p: p,
+        })
+    }
+
+    pub fn log_n(&self) -> u8 {
+        self.m_log_n
+    }
+
+    pub fn r(&self) -> u32 {
+        self.m_r
+    }
+
+    pub fn p(&self) -> u32 {
+        self.m_p
+    }
+}
+
+impl Default for ScryptKdf {
+    /// 默认N=2^17，r=8，p=1
+    fn default() -> Self {
+        ScryptKdf {
+            m_log_n: 17,
+            m_r: 8,
+            m_p: 1,
+        }
+    }
+}
+
+impl KeyDerivationTrait for ScryptKdf {
+    const SALT_LENGTH: usize = KDF_SALT_LENGTH;
+
+    fn derive_key(&self, password: &str, salt: &[u8], n: usize) -> Result<ByteVector> {
+        let params = ExScryptParams::new(self.m_log_n, self.m_r, self.m_p, n)
+            .map_err(|e| INVALID_KDF_PARAMETERS.add_opt_mess(&format!("{e}")))?;
+        let mut res = vec![0x00; n];
+        ex_scrypt(password.as_bytes(), salt, &params, &mut res)
+            .map_err(|e| FAILED_WHEN_DERIVING_KEY.add_opt_mess(&format!("{e}")))?;
+        Ok(res)
+    }
+}
+
+/// Argon2id
+#[derive(Clone)]
+pub struct Argon2idKdf {
+    /// 内存开销，单位为KiB
+    m_m_cost: u32,
+    /// 迭代次数
+    m_t_cost: u32,
+    /// 并行度
+    m_p_cost: u32,
+}
+
+impl Argon2idKdf {
+    pub fn new(m_cost: u32, t_cost: u32, p_cost: u32) -> Result<Self> {
+        ExArgon2Params::new(m_cost, t_cost, p_cost, None)
+            .map_err(|e| INVALID_KDF_PARAMETERS.add_opt_mess(&format!("{e}")))?;
+        Ok(Argon2idKdf {
+            m_m_cost: m_cost,
+            m_t_cost: t_cost,
+            m_p_cost: p_cost,
+        })
+    }
+
+    pub fn m_cost(&self) -> u32 {
+        self.m_m_cost
+    }
+
+    pub fn t_cost(&self) -> u32 {
+        self.m_t_cost
+    }
+
+    pub fn p_cost(&self) -> u32 {
+        self.m_p_cost
+    }
+}
+
+impl Default for Argon2idKdf {
+    /// 默认使用19MiB内存、迭代2次、并行度1
+    fn default() -> Self {
+        Argon2idKdf {
+            m_m_cost: 19 * 1024,
+            m_t_cost: 2,
+            m_p_cost: 1,
+        }
+    }
+}
+
+impl KeyDerivationTrait for Argon2idKdf {
+    const SALT_LENGTH: usize = KDF_SALT_LENGTH;
+
+    fn derive_key(&self, password: &str, salt: &[u8], n: usize) -> Result<ByteVector> {
+        let params = ExArgon2Params::new(self.m_m_cost, self.m_t_cost, self.m_p_cost, Some(n))
+            .map_err(|e| INVALID_KDF_PARAMETERS.add_opt_mess(&format!("{e}")))?;
+        let argon2 = ExArgon2::new(ExArgon2Algorithm::Argon2id, ExArgon2Version::V0x13, params);
+        let mut res = vec![0x00; n];
+        argon2
+            .hash_password_into(password.as_bytes(), salt, &mut res)
+            .map_err(|e| FAILED_WHEN_DERIVING_KEY.add_opt_mess(&format!("{e}")))?;
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypter::aead::{AeadStringCrypter, ChaCha20Poly1305AeadAlgorithm};
+    use crate::crypter::chacha20::ChaCha20CipherAlgorithm;
+    use crate::crypter::{StringCrypter, StringCrypterTrait};
+    use crate::encoding::base64::Base64Encoding;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use crate::hash::crc::Crc32cHasher;
+
+    #[test]
+    fn test_legacy_kdf() {
+        let hexe = HexEncoding::default();
+        let res = LegacySha256Kdf.derive_key("123456", &[], 32).unwrap();
+        assert_eq!(
+            "8D969EEF6ECAD3C29A3A629280E686CF0C3F5D5A86AFF3CA12020C923ADC6C92".to_string(),
+            hexe.encode(&res)
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_hmac_sha256_kdf() {
+        // RFC 7914 11. PBKDF2-HMAC-SHA256
+        let hexe = HexEncoding::default();
+        let kdf = Pbkdf2HmacSha256Kdf::new(1).unwrap();
+        let res = kdf.derive_key("passwd", b"salt", 64).unwrap();
+        assert_eq!(
+            "55AC046E56E3089FEC1691C22544B605F94185216DDE0465E68B9D57C20DACBC49CA9CCCF179B645991664B39D77EF317C71B845B1E30BD509112041D3A19783".to_string(),
+            hexe.encode(&res)
+        );
+        assert!(Pbkdf2HmacSha256Kdf::new(0).is_err());
+    }
+
+    #[test]
+    fn test_scrypt_kdf() {
+        // RFC 7914 12. scrypt (P="password", S="NaCl", N=1024, r=8, p=16)
+        let hexe = HexEncoding::default();
+        let kdf = ScryptKdf::new(10, 8, 16).unwrap();
+        let res = kdf.derive_key("password", b"NaCl", 64).unwrap();
+        assert_eq!(
+            "FDBABE1C9D3472007856E7190D01E9FE7C6AD7CBC8237830E77376634B3731622EAF30D92E22A3886FF109279D9830DAC727AFB94A83EE6D8360CBDFA2CC0640".to_string(),
+            hexe.encode(&res)
+        );
+        assert!(ScryptKdf::new(10, 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_argon2id_kdf() {
+        let kdf = Argon2idKdf::new(64, 1, 1).unwrap();
+        let salt = [0x01u8; KDF_SALT_LENGTH];
+        let res1 = kdf.derive_key("123456", &salt, 32).unwrap();
+        let res2 = kdf.derive_key("123456", &salt, 32).unwrap();
+        assert_eq!(res1, res2);
+        assert_eq!(res1.len(), 32);
+        // 不同的盐应当得到不同的密钥
+        let res3 = kdf
+            .derive_key("123456", &[0x02u8; KDF_SALT_LENGTH], 32)
+            .unwrap();
+        assert_ne!(res1, res3);
+        assert!(Argon2idKdf::new(1, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_string_crypter_with_kdf() {
+        let crypters: [Box<dyn StringCrypterTrait>; 4] = [
+            Box::new(
+                StringCrypter::<ChaCha20CipherAlgorithm, Crc32cHasher, _>::with_kdf(
+                    Pbkdf2HmacSha256Kdf::new(1000).unwrap(),
+                ),
+            ),
+            Box::new(
+                StringCrypter::<ChaCha20CipherAlgorithm, Crc32cHasher, _>::with_kdf(
+                    ScryptKdf::new(8, 8, 1).unwrap(),
+                ),
+            ),
+            Box::new(
+                StringCrypter::<ChaCha20CipherAlgorithm, Crc32cHasher, _>::with_kdf(
+                    Argon2idKdf::new(64, 1, 1).unwrap(),
+                ),
+            ),
+            Box::new(
+                AeadStringCrypter::<ChaCha20Poly1305AeadAlgorithm, _>::with_kdf(
+                    Argon2idKdf::new(64, 1, 1).unwrap(),
+                ),
+            ),
+        ];
+        let b64 = Base64Encoding::default();
+        for string_crypter in crypters {
+            let ciphertext1 = string_crypter.encrypt("123456", "123456").unwrap();
+            let ciphertext2 = string_crypter.encrypt("123456", "123456").unwrap();
+            // 每次加密使用不同的盐
+            assert_ne!(
+                b64.decode(&ciphertext1).unwrap()[..KDF_SALT_LENGTH],
+                b64.decode(&ciphertext2).unwrap()[..KDF_SALT_LENGTH]
+            );
+            assert_eq!(
+                string_crypter.decrypt(&ciphertext1, "123456").unwrap(),
+                "123456"
+            );
+            assert!(string_crypter.decrypt(&ciphertext1, "654321").is_err());
+        }
+    }
+
+    #[test]
+    fn test_legacy_ciphertext() {
+        // 旧版本StringCrypter<ChaCha20CipherAlgorithm>产生的密文仍然可以通过默认的密钥派生方式解密
+        let string_crypter = StringCrypter::<ChaCha20CipherAlgorithm>::default();
+        let plaintext = string_crypter
+            .decrypt(
+                "AAECAwQFBgcICQoLa9L5qBckP5uQGA8PntKJ5S8rPVWrtt/trtq6",
+                "123456",
+            )
+            .unwrap();
+        assert_eq!(plaintext, "Hello, ciftl! 你好！");
+    }
+}
@@ -1,6 +1,15 @@
+pub mod aead;
+pub mod aes;
+pub mod block;
 pub mod chacha20;
+pub mod factory;
+pub mod header;
+pub mod kdf;
+pub mod sm4;
+pub mod stream;
 
 use std::cmp::min;
+use std::io::{Read, Write};
 use std::marker;
 use std::vec;
 
@@ -8,21 +17,29 @@ use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use rand::prelude::*;
 
+use crate::crypter::kdf::LegacySha256Kdf;
 use crate::encoding::base64::Base64Encoding;
 use crate::encoding::EncodingTrait as _;
 use crate::hash::crc::Crc32cHasher;
 use crate::hash::crc::CRC32_OUTPUT_LENGTH;
-use crate::hash::sha256::Sha256Hasher;
 use crate::hash::HasherTrait;
 use crate::*;
 
-/// 目前支持的所有算法
+/// 目前支持的所有算法，其值同时作为密文头部中的算法标识，不应修改
+#[derive(FromPrimitive, Clone, Copy, PartialEq, Debug)]
 pub enum CipherAlgorithm {
-    ChaCha20,
+    ChaCha20 = 1,
+    AES256CTR = 2,
+    AES128OFB = 3,
+    AES256OFB = 4,
+    ChaCha20Poly1305 = 5,
+    AES256GCM = 6,
+    XChaCha20 = 7,
+    SM4OFB = 8,
 }
 
 /// 密码算法的类型
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum CipherAlgorithmType {
     Stream,
     Block(usize),
@@ -58,6 +75,12 @@ pub trait CipherAlgorithmTrait: CipherAlgorithmBaseTrait {
     fn crypt(&mut self, src_data: &[u8], dst_data: &mut [u8]) -> Result<()>;
 }
 
+/// 可以随机定位密码流位置的密码算法trait，只有流密码（或CTR等计数器模式）可以实现
+pub trait SeekableCipherAlgorithmTrait: CipherAlgorithmTrait {
+    /// 将密码流的位置移动到第pos个字节
+    fn seek(&mut self, pos: u64) -> Result<()>;
+}
+
 /// 通过IV和Key生成一个实例
 pub trait IVKeyNewTrait {
     /// 创建一个加密算法器
@@ -66,6 +89,43 @@ pub trait IVKeyNewTrait {
         Self: Sized;
 }
 
+/// 分组密码算法trait，只对单个分组进行原始的加解密，具体的工作模式由BlockCrypter实现
+pub trait BlockCipherAlgorithmTrait: CipherAlgorithmBaseTrait {
+    /// 加密一个分组，block的长度必须等于分组长度
+    fn encrypt_block(&self, block: &mut [u8]) -> Result<()>;
+    /// 解密一个分组，block的长度必须等于分组长度
+    fn decrypt_block(&self, block: &mut [u8]) -> Result<()>;
+}
+
+/// 通过Key生成一个实例
+pub trait KeyNewTrait {
+    /// 创建一个分组密码算法器
+    fn new(key: &[u8]) -> Result<Self>
+    where
+        Self: Sized;
+}
+
+/// 带认证的加密算法（AEAD）trait
+pub trait AeadAlgorithmTrait {
+    const NONCE_LENGTH: usize;
+    const KEY_LENGTH: usize;
+    const TAG_LENGTH: usize;
+
+    /// 加密并在密文末尾附加认证标签
+    fn seal(&self, nonce: &[u8], data: &[u8]) -> Result<ByteVector>;
+    /// 校验认证标签并解密，标签不匹配时返回错误
+    fn open(&self, nonce: &[u8], data: &[u8]) -> Result<ByteVector>;
+}
+
+/// 从密码派生密钥的trait
+pub trait KeyDerivationTrait {
+    /// 盐的长度，为0时表示不使用盐
+    const SALT_LENGTH: usize;
+
+    /// 由密码和盐派生出长度为n的密钥
+    fn derive_key(&self, password: &str, salt: &[u8], n: usize) -> Result<ByteVector>;
+}
+
 /// 密码流生成器的trait
 pub trait StreamGeneratorTrait {
     /// 生成密码流
@@ -78,6 +138,20 @@ pub trait StringCrypterTrait {
     fn decrypt(&self, data: &str, password: &str) -> Result<String>;
 }
 
+/// 字节加密器trait，输入输出均为原始字节，不做任何编码
+pub trait BytesCrypterTrait {
+    fn encrypt(&self, data: &[u8], password: &str) -> Result<ByteVector>;
+    fn decrypt(&self, data: &[u8], password: &str) -> Result<ByteVector>;
+}
+
+/// 数据流加密器trait，以固定大小的内存处理任意长度的数据流
+pub trait StreamCrypterTrait {
+    /// 加密src中的全部内容并写入dst，返回明文的长度
+    fn encrypt(&self, src: &mut dyn Read, dst: &mut dyn Write, password: &str) -> Result<u64>;
+    /// 解密src中的全部内容并写入dst，返回明文的长度
+    fn decrypt(&self, src: &mut dyn Read, dst: &mut dyn Write, password: &str) -> Result<u64>;
+}
+
 //------------------------------------------------具体实现------------------------------------------------//
 /// 密码流生成器模式
 #[derive(FromPrimitive, Clone)]
@@ -192,6 +266,59 @@ impl<CA: CipherAlgorithmTrait + IVKeyNewTrait> StreamGeneratorTrait for StreamGe
     }
 }
 
+/// SeekableStreamGenerator是可以随机定位的密码流生成器
+/// 定位后会丢弃缓冲区中已有的密码流，下次生成时从新的位置重新填充缓冲区
+pub struct SeekableStreamGenerator<CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait> {
+    /// 内部的密码流生成器
+    m_stream_generator: StreamGenerator<CA>,
+    /// 下一个生成的字节在密码流中的位置
+    m_position: u64,
+}
+
+impl<CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait> SeekableStreamGenerator<CA> {
+    /// 新建一个可以随机定位的密码流生成器
+    pub fn new(iv: &[u8], key: &[u8], mode: StreamGeneratorMode) -> Result<Self> {
+        Ok(SeekableStreamGenerator::<CA> {
+            m_stream_generator: StreamGenerator::<CA>::new(iv, key, mode)?,
+            m_position: 0,
+        })
+    }
+
+    /// 将密码流的位置移动到第pos个字节
+    pub fn seek(&mut self, pos: u64) -> Result<()> {
+        let stream_generator = &mut self.m_stream_generator;
+        stream_generator.m_cipher_algorithm.seek(pos)?;
+        // 缓冲区中的内容不再有效，将下标移动到缓冲区的最后使下次生成时刷新
+        stream_generator.m_current_index = stream_generator.m_max_buffer_size;
+        self.m_position = pos;
+        Ok(())
+    }
+
+    /// 当前在密码流中的位置
+    pub fn position(&self) -> u64 {
+        self.m_position
+    }
+}
+
+impl<CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait> IVKeyNewTrait
+    for SeekableStreamGenerator<CA>
+{
+    /// 新建一个可以随机定位的密码流生成器
+    fn new(iv: &[u8], key: &[u8]) -> Result<Self> {
+        Self::new(iv, key, StreamGeneratorMode::Medium)
+    }
+}
+
+impl<CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait> StreamGeneratorTrait
+    for SeekableStreamGenerator<CA>
+{
+    fn generate(&mut self, len: usize) -> Result<ByteVector> {
+        let res = self.m_stream_generator.generate(len)?;
+        self.m_position += len as u64;
+        Ok(res)
+    }
+}
+
 /// 随机生成IV
 #[inline]
 fn rand_iv(n: usize) -> ByteVector {
@@ -201,8 +328,8 @@ fn rand_iv(n: usize) -> ByteVector {
     res
 }
 
-/// 该函数用于从密码生成密钥流，其原理是将密码进行sha256哈希，将哈希值拷贝到给定长度为N的ByteArray中
-/// 如果一次sha256的长度不足N，则将上一次的哈希值与原文拼接继续进行一次哈希，并将值继续拷贝到ByteArray中，重复该步骤直到长度大于N为止
+/// 该函数用于从密码生成密钥流，其原理是使用哈希算法HR对密码进行哈希，将哈希值拷贝到给定长度为N的ByteArray中
+/// 如果一次哈希的长度不足N，则将上一次的哈希值与原文拼接继续进行一次哈希，并将值继续拷贝到ByteArray中，重复该步骤直到长度大于N为止
 #[inline]
 fn generate_key_from_password<HR: HasherTrait + Default>(password: &str, n: usize) -> ByteVector {
     // 生成加密所需的密钥
@@ -229,92 +356,128 @@ fn generate_key_from_password<HR: HasherTrait + Default>(password: &str, n: usiz
     res
 }
 
-/// StringCrypter是ciftl自己实现的一个文本加密器
-pub struct StringCrypter<
+/// 使用字节加密器加密文本，结果为Base64编码
+fn encrypt_string<BC: BytesCrypterTrait + ?Sized>(
+    bytes_crypter: &BC,
+    data: &str,
+    password: &str,
+) -> Result<String> {
+    if data.is_empty() {
+        return Err(CANNOT_ENCRYPT_EMPTY_STRING.clone());
+    }
+    let res = bytes_crypter.encrypt(data.as_bytes(), password)?;
+    // 对结果进行编码
+    Ok(Base64Encoding::default().encode(&res))
+}
+
+/// 使用字节加密器解密Base64编码的密文，要求明文为UTF-8文本
+fn decrypt_string<BC: BytesCrypterTrait + ?Sized>(
+    bytes_crypter: &BC,
+    data: &str,
+    password: &str,
+) -> Result<String> {
+    // 对密文进行解码
+    let data = Base64Encoding::default().decode(data)?;
+    let plain_data_bytes = bytes_crypter.decrypt(&data, password)?;
+    String::from_utf8(plain_data_bytes)
+        .map_err(|e| FAILED_WHEN_DECODING_STRING.add_opt_mess(&format!("{:?}", e)))
+}
+
+/// BytesCrypter是ciftl自己实现的一个字节加密器
+/// 输出为salt || iv || 校验值 || 密文，salt的长度由KD决定，默认的LegacySha256Kdf不使用盐
+pub struct BytesCrypter<
     CA: CipherAlgorithmTrait + IVKeyNewTrait,
     HR: HasherTrait + Default = Crc32cHasher,
+    KD: KeyDerivationTrait + Default = LegacySha256Kdf,
 > {
     _ca: marker::PhantomData<CA>,
     _hr: marker::PhantomData<HR>,
+    /// 密钥派生算法
+    m_kdf: KD,
 }
 
-impl<CA: CipherAlgorithmTrait + IVKeyNewTrait, HR: HasherTrait + Default> StringCrypter<CA, HR> {
-    pub fn rand_iv(n: usize) -> ByteVector {
-        crate::crypter::rand_iv(n)
-    }
-
-    pub fn generate_key_from_password(password: &str, n: usize) -> ByteVector {
-        generate_key_from_password::<Sha256Hasher>(password, n)
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: HasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > BytesCrypter<CA, HR, KD>
+{
+    /// 使用指定参数的密钥派生算法创建字节加密器
+    pub fn with_kdf(kdf: KD) -> Self {
+        BytesCrypter::<CA, HR, KD> {
+            _ca: marker::PhantomData::<CA>,
+            _hr: marker::PhantomData::<HR>,
+            m_kdf: kdf,
+        }
     }
 }
 
-impl<CA: CipherAlgorithmTrait + IVKeyNewTrait, HR: HasherTrait + Default> Default
-    for StringCrypter<CA, HR>
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: HasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > Default for BytesCrypter<CA, HR, KD>
 {
     fn default() -> Self {
-        StringCrypter::<CA, HR> {
-            _ca: marker::PhantomData::<CA>,
-            _hr: marker::PhantomData::<HR>,
-        }
+        Self::with_kdf(KD::default())
     }
 }
 
-impl<CA: CipherAlgorithmTrait + IVKeyNewTrait, HR: HasherTrait + Default> StringCrypterTrait
-    for StringCrypter<CA, HR>
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: HasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > BytesCrypterTrait for BytesCrypter<CA, HR, KD>
 {
-    fn encrypt(&self, data: &str, password: &str) -> Result<String> {
-        if data.is_empty()
-        {
-            return Err(CANNOT_ENCRYPT_EMPTY_STRING.clone());
-        }
+    fn encrypt(&self, data: &[u8], password: &str) -> Result<ByteVector> {
         // 创建一个密码流生成器
+        let salt = rand_iv(KD::SALT_LENGTH);
         let iv = rand_iv(CA::IV_LENGTH);
-        let key = Self::generate_key_from_password(password, CA::KEY_LENGTH);
+        let key = self.m_kdf.derive_key(password, &salt, CA::KEY_LENGTH)?;
         let mut stream_generator =
             StreamGenerator::<CA>::new(&iv, &key, StreamGeneratorMode::Short)?;
-        // 获取明文的字节流
-        let plain_data_bytes = data.as_bytes();
         // 获取明文的校验值
         let plain_data_checksum = {
             let mut c = HR::default();
-            c.update_bytes(&plain_data_bytes);
+            c.update_bytes(data);
             c.finalize()
         };
         // 生成密码流进行加密
         let cipher_data_bytes = {
-            let cipher_stream = stream_generator.generate(plain_data_bytes.len())?;
-            xor(&cipher_stream, plain_data_bytes)?
+            let cipher_stream = stream_generator.generate(data.len())?;
+            xor(&cipher_stream, data)?
         };
         // 继续加密校验值
         let cipher_data_checksum = {
             let cipher_stream = stream_generator.generate(plain_data_checksum.len())?;
             xor(&cipher_stream, &plain_data_checksum)?
         };
-        let res = [&iv[..], &cipher_data_checksum[..], &cipher_data_bytes[..]].concat();
-        // 对结果进行编码
-        Ok(Base64Encoding::default().encode(&res))
+        Ok([
+            &salt[..],
+            &iv[..],
+            &cipher_data_checksum[..],
+            &cipher_data_bytes[..],
+        ]
+        .concat())
     }
 
-    fn decrypt(&self, data: &str, password: &str) -> Result<String> {
-        // 对密文进行解码
-        let b64 = Base64Encoding::default();
-        let data = b64.decode(data)?;
+    fn decrypt(&self, data: &[u8], password: &str) -> Result<ByteVector> {
         // 从原文中获取数据
+        let mut salt = vec![0u8; KD::SALT_LENGTH];
         let mut iv = vec![0u8; CA::IV_LENGTH];
         let mut cipher_data_checksum = vec![0u8; HR::OUTPUT_LENGTH];
-        let mut mt = MemoryTaker::new(&data);
-        let cipher_data_bytes = mt
-            .take(&mut iv)?
-            .take(&mut cipher_data_checksum)?
-            .take_all()?;
+        let mut mt = MemoryTaker::new(data);
+        mt.take(&mut salt)?.take(&mut iv)?.take(&mut cipher_data_checksum)?;
+        // 明文允许为空，所以剩余的密文可能为空
+        let cipher_data_bytes = &data[KD::SALT_LENGTH + CA::IV_LENGTH + HR::OUTPUT_LENGTH..];
         // 创建一个密码流生成器
-        let key = Self::generate_key_from_password(password, CA::KEY_LENGTH);
+        let key = self.m_kdf.derive_key(password, &salt, CA::KEY_LENGTH)?;
         let mut stream_generator =
             StreamGenerator::<CA>::new(&iv, &key, StreamGeneratorMode::Short)?;
         // 生成密码流进行解密
         let plain_data_bytes = {
             let cipher_stream = stream_generator.generate(cipher_data_bytes.len())?;
-            xor(&cipher_data_bytes, &cipher_stream)?
+            xor(cipher_data_bytes, &cipher_stream)?
         };
         // 解密原文的校验值
         let plain_data_checksum = {
@@ -330,8 +493,69 @@ impl<CA: CipherAlgorithmTrait + IVKeyNewTrait, HR: HasherTrait + Default> String
         if plain_data_checksum != calced_plain_data_checksum {
             return Err(FAILED_WHEN_CHECKING_CRC32_VALUE_OF_DECRYPTED_CONTENT.clone());
         }
-        Ok(String::from_utf8(plain_data_bytes)
-            .map_err(|e| FAILED_WHEN_DECODING_STRING.add_opt_mess(&format!("{:?}", e)))?)
+        Ok(plain_data_bytes)
+    }
+}
+
+/// StringCrypter是ciftl自己实现的一个文本加密器
+/// 它是BytesCrypter的一层包装，输出为BytesCrypter结果的Base64编码
+pub struct StringCrypter<
+    CA: CipherAlgorithmTrait + IVKeyNewTrait,
+    HR: HasherTrait + Default = Crc32cHasher,
+    KD: KeyDerivationTrait + Default = LegacySha256Kdf,
+> {
+    m_bytes_crypter: BytesCrypter<CA, HR, KD>,
+}
+
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: HasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > StringCrypter<CA, HR, KD>
+{
+    /// 使用指定参数的密钥派生算法创建文本加密器
+    pub fn with_kdf(kdf: KD) -> Self {
+        StringCrypter::<CA, HR, KD> {
+            m_bytes_crypter: BytesCrypter::<CA, HR, KD>::with_kdf(kdf),
+        }
+    }
+
+    pub fn rand_iv(n: usize) -> ByteVector {
+        crate::crypter::rand_iv(n)
+    }
+
+    /// 使用哈希算法H从密码生成长度为n的密钥，H为Sha256Hasher时与LegacySha256Kdf相同
+    pub fn generate_key_from_password<H: HasherTrait + Default>(
+        password: &str,
+        n: usize,
+    ) -> ByteVector {
+        generate_key_from_password::<H>(password, n)
+    }
+}
+
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: HasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > Default for StringCrypter<CA, HR, KD>
+{
+    fn default() -> Self {
+        Self::with_kdf(KD::default())
+    }
+}
+
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: HasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > StringCrypterTrait for StringCrypter<CA, HR, KD>
+{
+    fn encrypt(&self, data: &str, password: &str) -> Result<String> {
+        encrypt_string(&self.m_bytes_crypter, data, password)
+    }
+
+    fn decrypt(&self, data: &str, password: &str) -> Result<String> {
+        decrypt_string(&self.m_bytes_crypter, data, password)
     }
 }
 
@@ -339,8 +563,103 @@ impl<CA: CipherAlgorithmTrait + IVKeyNewTrait, HR: HasherTrait + Default> String
 mod tests {
 
     use super::*;
+    use crate::crypter::aes::{
+        Aes128OfbCipherAlgorithm, Aes256CtrCipherAlgorithm, Aes256OfbCipherAlgorithm,
+    };
+    use crate::crypter::chacha20::{ChaCha20CipherAlgorithm, XChaCha20CipherAlgorithm};
     use crate::encoding::hex::HexEncoding;
     use crate::encoding::EncodingTrait;
+    use crate::hash::blake2::Blake2b512Hasher;
+    use crate::hash::blake3::Blake3Hasher;
+    use crate::hash::sha256::Sha256Hasher;
+
+    /// 以不规则的长度分段生成密码流，拼接后的结果应与模式无关
+    fn generate_with_mode<CA: CipherAlgorithmTrait + IVKeyNewTrait>(
+        mode: StreamGeneratorMode,
+    ) -> ByteVector {
+        let iv = vec![0x11; CA::IV_LENGTH];
+        let key = vec![0x22; CA::KEY_LENGTH];
+        let mut stream_generator = StreamGenerator::<CA>::new(&iv, &key, mode).unwrap();
+        let mut res = ByteVector::new();
+        for len in [0, 1, 15, 17, 1000, 4097, 70000, 3] {
+            res.extend(stream_generator.generate(len).unwrap());
+        }
+        res
+    }
+
+    fn assert_same_stream_in_all_modes<CA: CipherAlgorithmTrait + IVKeyNewTrait>() {
+        let short = generate_with_mode::<CA>(StreamGeneratorMode::Short);
+        let medium = generate_with_mode::<CA>(StreamGeneratorMode::Medium);
+        let large = generate_with_mode::<CA>(StreamGeneratorMode::Large);
+        assert_eq!(short, medium);
+        assert_eq!(short, large);
+    }
+
+    #[test]
+    fn test_stream_generator_modes() {
+        assert_same_stream_in_all_modes::<ChaCha20CipherAlgorithm>();
+        assert_same_stream_in_all_modes::<XChaCha20CipherAlgorithm>();
+        assert_same_stream_in_all_modes::<Aes128OfbCipherAlgorithm>();
+        assert_same_stream_in_all_modes::<Aes256OfbCipherAlgorithm>();
+    }
+
+    fn assert_seekable<CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait>() {
+        let iv = vec![0x11; CA::IV_LENGTH];
+        let key = vec![0x22; CA::KEY_LENGTH];
+        let full = generate_with_mode::<CA>(StreamGeneratorMode::Short);
+        let mut stream_generator =
+            SeekableStreamGenerator::<CA>::new(&iv, &key, StreamGeneratorMode::Short).unwrap();
+        // 向前和向后定位，包括跨越缓冲区和不按分组对齐的位置
+        for (pos, len) in [(70000, 100), (0, 10), (1, 4096), (4095, 3), (65537, 9000), (3, 0)] {
+            stream_generator.seek(pos as u64).unwrap();
+            assert_eq!(stream_generator.generate(len).unwrap(), full[pos..pos + len]);
+            assert_eq!(stream_generator.position(), (pos + len) as u64);
+        }
+        // 定位后继续顺序生成
+        stream_generator.seek(100).unwrap();
+        let mut res = stream_generator.generate(5000).unwrap();
+        res.extend(stream_generator.generate(5000).unwrap());
+        assert_eq!(res, full[100..10100]);
+    }
+
+    #[test]
+    fn test_seekable_stream_generator() {
+        assert_seekable::<ChaCha20CipherAlgorithm>();
+        assert_seekable::<Aes256CtrCipherAlgorithm>();
+        assert_seekable::<XChaCha20CipherAlgorithm>();
+    }
+
+    #[test]
+    fn test_bytes_crypter() {
+        let bytes_crypter = BytesCrypter::<ChaCha20CipherAlgorithm>::default();
+        // 非UTF-8的二进制内容和空内容都可以加密
+        for plain_data in [vec![0xFF, 0xFE, 0x00, 0x80, 0xC3], vec![]] {
+            let cipher_data = bytes_crypter.encrypt(&plain_data, "123456").unwrap();
+            assert_eq!(
+                bytes_crypter.decrypt(&cipher_data, "123456").unwrap(),
+                plain_data
+            );
+            let res = bytes_crypter.decrypt(&cipher_data, "654321");
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+        }
+        // 长度不足以容纳iv和校验值的密文
+        assert!(bytes_crypter.decrypt(&[0x00; 15], "123456").is_err());
+        // StringCrypter的结果解码后与BytesCrypter的格式相同
+        let string_crypter = StringCrypter::<ChaCha20CipherAlgorithm>::default();
+        let cipher_data = Base64Encoding::default()
+            .decode(&string_crypter.encrypt("123456", "123456").unwrap())
+            .unwrap();
+        assert_eq!(
+            bytes_crypter.decrypt(&cipher_data, "123456").unwrap(),
+            b"123456".to_vec()
+        );
+        // 解密得到非UTF-8内容时StringCrypter返回错误
+        let cipher_data = bytes_crypter.encrypt(&[0xFF, 0xFE], "123456").unwrap();
+        let res = string_crypter.decrypt(&Base64Encoding::default().encode(&cipher_data), "123456");
+        assert!(res.is_err());
+        println!("错误：{}", res.unwrap_err());
+    }
 
     #[test]
     fn test_generate_key_from_password() {
@@ -354,5 +673,31 @@ mod tests {
         // 123456 48bytes
         let res = generate_key_from_password::<Sha256Hasher>("123456", 48);
         assert_eq!("8D969EEF6ECAD3C29A3A629280E686CF0C3F5D5A86AFF3CA12020C923ADC6C9213619CFEA04EEB088EA04D789731EFED".to_string(), hexe.encode(&res));
+        // 其他哈希算法同样可以用于生成密钥
+        type Crypter = StringCrypter<ChaCha20CipherAlgorithm>;
+        let res = Crypter::generate_key_from_password::<Blake3Hasher>("123456", 48);
+        assert_eq!(res.len(), 48);
+        assert_eq!(res, generate_key_from_password::<Blake3Hasher>("123456", 48));
+        assert_ne!(
+            res,
+            Crypter::generate_key_from_password::<Blake2b512Hasher>("123456", 48)
+        );
+        assert_eq!(
+            Crypter::generate_key_from_password::<Sha256Hasher>("123456", 32),
+            generate_key_from_password::<Sha256Hasher>("123456", 32)
+        );
+    }
+
+    #[test]
+    fn test_string_crypter_with_blake() {
+        let string_crypter = StringCrypter::<ChaCha20CipherAlgorithm, Blake2b512Hasher>::default();
+        let cipher_text = string_crypter.encrypt("123456", "123456").unwrap();
+        assert_eq!(string_crypter.decrypt(&cipher_text, "123456").unwrap(), "123456");
+        let string_crypter = StringCrypter::<Aes256CtrCipherAlgorithm, Blake3Hasher>::default();
+        let cipher_text = string_crypter.encrypt("123456", "123456").unwrap();
+        assert_eq!(string_crypter.decrypt(&cipher_text, "123456").unwrap(), "123456");
+        let res = string_crypter.decrypt(&cipher_text, "654321");
+        assert!(res.is_err());
+        println!("错误：{}", res.unwrap_err());
     }
 }
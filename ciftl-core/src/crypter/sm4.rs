@@ -0,0 +1,182 @@
+use ::sm4::cipher::{Block, BlockDecrypt, BlockEncrypt, KeyInit};
+use ::sm4::Sm4 as ExSm4;
+
+use crate::crypter::{
+    BlockCipherAlgorithmTrait, CipherAlgorithmBaseTrait, CipherAlgorithmTrait, CipherAlgorithmType,
+    IVKeyNewTrait, KeyNewTrait,
+};
+use crate::*;
+
+/// SM4的分组长度
+pub const SM4_BLOCK_LENGTH: usize = 16;
+/// SM4的Key长度
+pub const SM4_KEY_LENGTH: usize = 16;
+/// SM4OFB的IV长度
+pub const SM4OFB_IV_LENGTH: usize = SM4_BLOCK_LENGTH;
+
+/// 创建SM4分组密码
+fn new_sm4(key: &[u8]) -> Result<ExSm4> {
+    if key.len() != SM4_KEY_LENGTH {
+        return Err(CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.clone());
+    }
+    ExSm4::new_from_slice(key)
+        .map_err(|e| CIPHER_ALGORITHM_UNSATISFIED_KEY_LENGTH.add_opt_mess(&format!("{e}")))
+}
+
+/// SM4的OFB模式，以分组密码的方式接入StreamGenerator
+/// 每次对反馈寄存器加密得到一个分组的密码流，因此输入的长度必须为分组长度的整数倍
+/// 注意：sm4 crate以查S盒的方式实现，不是常数时间的，攻击者能在同一台机器上测量计时时不应使用
+pub struct Sm4OfbCipherAlgorithm {
+    m_algo: ExSm4,
+    /// 反馈寄存器，初始值为IV
+    m_feedback: Block<ExSm4>,
+}
+
+impl CipherAlgorithmBaseTrait for Sm4OfbCipherAlgorithm {
+    const IV_LENGTH: usize = SM4OFB_IV_LENGTH;
+    const KEY_LENGTH: usize = SM4_KEY_LENGTH;
+    const CIPHER_ALGORITHM_TYPE: CipherAlgorithmType = CipherAlgorithmType::Block(SM4_BLOCK_LENGTH);
+}
+
+impl CipherAlgorithmTrait for Sm4OfbCipherAlgorithm {
+    fn crypt(&mut self, src_data: &[u8], dst_data: &mut [u8]) -> Result<()> {
+        if !src_data.len().is_multiple_of(SM4_BLOCK_LENGTH) {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        memcpy(dst_data, src_data)?;
+        for block in dst_data.chunks_mut(SM4_BLOCK_LENGTH) {
+            // O_i = E(K, O_{i-1})，C_i = P_i ^ O_i
+            self.m_algo.encrypt_block(&mut self.m_feedback);
+            for (d, k) in block.iter_mut().zip(self.m_feedback.iter()) {
+                *d ^= *k;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IVKeyNewTrait for Sm4OfbCipherAlgorithm {
+    fn new(iv: &[u8], key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if iv.len() != Self::IV_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH.clone());
+        }
+        Ok(Sm4OfbCipherAlgorithm {
+            m_algo: new_sm4(key)?,
+            m_feedback: Block::<ExSm4>::clone_from_slice(iv),
+        })
+    }
+}
+
+/// SM4分组密码本身，供BlockCrypter以ECB、CBC、CFB等模式使用
+/// 注意：sm4 crate以查S盒的方式实现，不是常数时间的，攻击者能在同一台机器上测量计时时不应使用
+pub struct Sm4BlockCipherAlgorithm {
+    m_algo: ExSm4,
+}
+
+impl CipherAlgorithmBaseTrait for Sm4BlockCipherAlgorithm {
+    // 分组模式下IV的长度与分组长度相同
+    const IV_LENGTH: usize = SM4_BLOCK_LENGTH;
+    const KEY_LENGTH: usize = SM4_KEY_LENGTH;
+    const CIPHER_ALGORITHM_TYPE: CipherAlgorithmType = CipherAlgorithmType::Block(SM4_BLOCK_LENGTH);
+}
+
+impl BlockCipherAlgorithmTrait for Sm4BlockCipherAlgorithm {
+    fn encrypt_block(&self, block: &mut [u8]) -> Result<()> {
+        if block.len() != SM4_BLOCK_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        self.m_algo
+            .encrypt_block(Block::<ExSm4>::from_mut_slice(block));
+        Ok(())
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> Result<()> {
+        if block.len() != SM4_BLOCK_LENGTH {
+            return Err(CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH.clone());
+        }
+        self.m_algo
+            .decrypt_block(Block::<ExSm4>::from_mut_slice(block));
+        Ok(())
+    }
+}
+
+impl KeyNewTrait for Sm4BlockCipherAlgorithm {
+    fn new(key: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Sm4BlockCipherAlgorithm {
+            m_algo: new_sm4(key)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypter::block::{BlockCipherMode, BlockCrypter, PaddingScheme};
+    use crate::crypter::{StringCrypter, StringCrypterTrait};
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use crate::hash::sm3::Sm3Hasher;
+
+    #[test]
+    fn test_sm4_block() {
+        // GB/T 32907-2016 附录A.1
+        let hexe = HexEncoding::default();
+        let key = hexe.decode("0123456789ABCDEFFEDCBA9876543210").unwrap();
+        let algo = Sm4BlockCipherAlgorithm::new(&key).unwrap();
+        let mut block = key.clone();
+        algo.encrypt_block(&mut block).unwrap();
+        assert_eq!(hexe.encode(&block), "681EDF34D206965E86B3E94F536E4246");
+        algo.decrypt_block(&mut block).unwrap();
+        assert_eq!(block, key);
+        assert!(algo.encrypt_block(&mut block[..15]).is_err());
+        assert!(Sm4BlockCipherAlgorithm::new(&key[..15]).is_err());
+        // CBC模式
+        let iv = hexe.decode("000102030405060708090A0B0C0D0E0F").unwrap();
+        let plaintext = hexe
+            .decode("6BC1BEE22E409F96E93D7E117393172AAE2D8A571E03AC9C9EB76FAC45AF8E51")
+            .unwrap();
+        let crypter = BlockCrypter::<Sm4BlockCipherAlgorithm>::new(
+            BlockCipherMode::CBC,
+            PaddingScheme::NoPadding,
+        );
+        let ciphertext = crypter.encrypt(&iv, &key, &plaintext).unwrap();
+        assert_eq!(
+            hexe.encode(&ciphertext),
+            "784626C834AB18614677EB2074F2C5575146022D81CD18FEF9BC1A1FD3A64D61"
+        );
+        assert_eq!(crypter.decrypt(&iv, &key, &ciphertext).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_sm4_ofb() {
+        let hexe = HexEncoding::default();
+        let key = hexe.decode("0123456789ABCDEFFEDCBA9876543210").unwrap();
+        let iv = hexe.decode("000102030405060708090A0B0C0D0E0F").unwrap();
+        let plaintext = hexe
+            .decode("6BC1BEE22E409F96E93D7E117393172AAE2D8A571E03AC9C9EB76FAC45AF8E51")
+            .unwrap();
+        let mut ciphertext = vec![0u8; plaintext.len()];
+        let mut algo = Sm4OfbCipherAlgorithm::new(&iv, &key).unwrap();
+        algo.crypt(&plaintext, &mut ciphertext).unwrap();
+        assert_eq!(
+            hexe.encode(&ciphertext),
+            "6D59228313E6F73BC3B08993923BEE405DC2C81BA980F6E1FFE88338988C6671"
+        );
+        // 不足一个分组时报错
+        assert!(algo.crypt(&plaintext[..15], &mut ciphertext[..15]).is_err());
+        // 配合SM3校验值在StringCrypter中使用
+        let string_crypter = StringCrypter::<Sm4OfbCipherAlgorithm, Sm3Hasher>::default();
+        let ciphertext = string_crypter.encrypt("123456", "123456").unwrap();
+        let plaintext = string_crypter.decrypt(&ciphertext, "123456").unwrap();
+        assert_eq!(plaintext, "123456");
+        let res = string_crypter.decrypt(&ciphertext, "654321");
+        assert!(res.is_err());
+        println!("错误：{}", res.unwrap_err());
+    }
+}
@@ -0,0 +1,680 @@
+//! 分块的数据流加密，每个数据块附带一个HMAC认证码（Encrypt-then-MAC）
+
+use std::cmp::{max, min};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::marker;
+use std::mem;
+
+use crate::crypter::kdf::LegacySha256Kdf;
+use crate::crypter::{
+    rand_iv, CipherAlgorithmTrait, IVKeyNewTrait, KeyDerivationTrait, SeekableCipherAlgorithmTrait,
+    SeekableStreamGenerator, StreamCrypterTrait, StreamGenerator, StreamGeneratorMode,
+    StreamGeneratorTrait,
+};
+use crate::hash::hmac::Hmac;
+use crate::hash::sha256::Sha256Hasher;
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// 默认的数据块长度
+pub const STREAM_CRYPTER_DEFAULT_CHUNK_LENGTH: usize = 64 * 1024;
+/// 数据块长度的上限，用于限制解密时的内存占用
+pub const STREAM_CRYPTER_MAX_CHUNK_LENGTH: usize = 16 * 1024 * 1024;
+/// 认证码密钥的长度
+pub const STREAM_CRYPTER_MAC_KEY_LENGTH: usize = 32;
+
+/// 从src中读取数据直到填满buf或遇到结尾，返回读取到的长度
+fn read_full(src: &mut dyn Read, buf: &mut [u8]) -> Result<usize> {
+    let mut n = 0;
+    while n < buf.len() {
+        match src.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(m) => n += m,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(FAILED_WHEN_READING_STREAM.add_opt_mess(&format!("{e}"))),
+        }
+    }
+    Ok(n)
+}
+
+fn write_all(dst: &mut dyn Write, data: &[u8]) -> Result<()> {
+    dst.write_all(data)
+        .map_err(|e| FAILED_WHEN_WRITING_STREAM.add_opt_mess(&format!("{e}")))
+}
+
+/// 头部的内容，即salt || iv || 数据块长度(u32大端)
+fn header_bytes(salt: &[u8], iv: &[u8], chunk_length: usize) -> ByteVector {
+    [salt, iv, &(chunk_length as u32).to_be_bytes()].concat()
+}
+
+/// 计算数据块的认证码，认证的是密文（Encrypt-then-MAC）
+/// 头部、块序号和是否为最后一块也参与计算，这样头部被修改或数据块被截断、丢弃、重排时都无法通过校验
+struct ChunkMac<HR: BlockHasherTrait + Default> {
+    m_mac: Hmac<HR>,
+    m_header: ByteVector,
+}
+
+impl<HR: BlockHasherTrait + Default> ChunkMac<HR> {
+    fn new(mac_key: &[u8], header: ByteVector) -> Self {
+        ChunkMac::<HR> {
+            m_mac: Hmac::<HR>::new(mac_key),
+            m_header: header,
+        }
+    }
+
+    fn update(&mut self, index: u64, is_last: bool, cipher_data: &[u8]) {
+        self.m_mac.update_bytes(&self.m_header);
+        self.m_mac.update_bytes(&index.to_be_bytes());
+        self.m_mac.update_bytes(&[is_last as u8]);
+        self.m_mac.update_bytes(cipher_data);
+    }
+
+    /// 计算一个数据块的认证码
+    fn tag(&mut self, index: u64, is_last: bool, cipher_data: &[u8]) -> ByteVector {
+        self.update(index, is_last, cipher_data);
+        self.m_mac.finalize_reset()
+    }
+
+    /// 以常量时间比较一个数据块的认证码
+    fn verify(&mut self, index: u64, is_last: bool, cipher_data: &[u8], tag: &[u8]) -> bool {
+        self.update(index, is_last, cipher_data);
+        let res = self.m_mac.verify(tag);
+        self.m_mac.reset();
+        res
+    }
+}
+
+/// 校验一个由认证码和密文组成的数据块并解密
+fn open_chunk<HR: BlockHasherTrait + Default>(
+    stream_generator: &mut dyn StreamGeneratorTrait,
+    mac: &mut ChunkMac<HR>,
+    index: u64,
+    is_last: bool,
+    chunk: &[u8],
+) -> Result<ByteVector> {
+    if chunk.len() < HR::OUTPUT_LENGTH {
+        return Err(STREAM_INTEGRITY_CHECK_FAILED.add_opt_mess("数据块不完整"));
+    }
+    let (tag, cipher_data_bytes) = chunk.split_at(HR::OUTPUT_LENGTH);
+    // 先校验认证码，通过后再解密
+    if !mac.verify(index, is_last, cipher_data_bytes, tag) {
+        return Err(
+            STREAM_INTEGRITY_CHECK_FAILED.add_opt_mess(&format!("第{}个数据块无法通过校验", index))
+        );
+    }
+    let cipher_stream = stream_generator.generate(cipher_data_bytes.len())?;
+    xor(cipher_data_bytes, &cipher_stream)
+}
+
+/// 由头部和密文长度得到的数据流布局
+struct StreamLayout {
+    m_salt: ByteVector,
+    m_iv: ByteVector,
+    /// 数据块长度
+    m_chunk_length: usize,
+    /// 明文的长度
+    m_plain_length: u64,
+    /// 数据块的数量
+    m_chunk_count: u64,
+}
+
+/// StreamCrypter是ciftl自己实现的一个数据流加密器，内存占用只与数据块长度有关
+/// 输出为salt || iv || 数据块长度(u32大端) || 数据块...，每个数据块为认证码 || 密文
+/// 除最后一块外，每个数据块的明文长度都等于数据块长度
+/// 认证码为HMAC-HR，其密钥与加密密钥由同一次密钥派生得到，不知道密码就无法修改密文或头部
+pub struct StreamCrypter<
+    CA: CipherAlgorithmTrait + IVKeyNewTrait,
+    HR: BlockHasherTrait + Default = Sha256Hasher,
+    KD: KeyDerivationTrait + Default = LegacySha256Kdf,
+> {
+    _ca: marker::PhantomData<CA>,
+    _hr: marker::PhantomData<HR>,
+    /// 密钥派生算法
+    m_kdf: KD,
+    /// 加密时使用的数据块长度
+    m_chunk_length: usize,
+}
+
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: BlockHasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > StreamCrypter<CA, HR, KD>
+{
+    /// 使用指定的密钥派生算法和数据块长度创建数据流加密器
+    pub fn new(kdf: KD, chunk_length: usize) -> Result<Self> {
+        if chunk_length == 0 || chunk_length > STREAM_CRYPTER_MAX_CHUNK_LENGTH {
+            return Err(INVALID_STREAM_CHUNK_LENGTH.clone());
+        }
+        Ok(StreamCrypter::<CA, HR, KD> {
+            _ca: marker::PhantomData::<CA>,
+            _hr: marker::PhantomData::<HR>,
+            m_kdf: kdf,
+            m_chunk_length: chunk_length,
+        })
+    }
+
+    /// 使用指定参数的密钥派生算法和默认的数据块长度创建数据流加密器
+    pub fn with_kdf(kdf: KD) -> Self {
+        StreamCrypter::<CA, HR, KD> {
+            _ca: marker::PhantomData::<CA>,
+            _hr: marker::PhantomData::<HR>,
+            m_kdf: kdf,
+            m_chunk_length: STREAM_CRYPTER_DEFAULT_CHUNK_LENGTH,
+        }
+    }
+
+    pub fn chunk_length(&self) -> usize {
+        self.m_chunk_length
+    }
+
+    /// 头部的长度
+    fn header_length() -> usize {
+        KD::SALT_LENGTH + CA::IV_LENGTH + 4
+    }
+
+    /// 由密码派生加密密钥和认证码密钥，返回加密密钥和用于计算认证码的HMAC
+    fn derive_keys(
+        &self,
+        password: &str,
+        salt: &[u8],
+        iv: &[u8],
+        chunk_length: usize,
+    ) -> Result<(ByteVector, ChunkMac<HR>)> {
+        let mut key = self.m_kdf.derive_key(
+            password,
+            salt,
+            CA::KEY_LENGTH + STREAM_CRYPTER_MAC_KEY_LENGTH,
+        )?;
+        let mac_key = key.split_off(CA::KEY_LENGTH);
+        let mac = ChunkMac::<HR>::new(&mac_key, header_bytes(salt, iv, chunk_length));
+        Ok((key, mac))
+    }
+
+    /// 读取头部，返回salt、iv和数据块长度
+    fn read_header(src: &mut dyn Read) -> Result<(ByteVector, ByteVector, usize)> {
+        let mut salt = vec![0u8; KD::SALT_LENGTH];
+        let mut iv = vec![0u8; CA::IV_LENGTH];
+        let mut chunk_length = [0u8; 4];
+        for field in [&mut salt[..], &mut iv[..], &mut chunk_length[..]] {
+            if read_full(src, field)? != field.len() {
+                return Err(STREAM_INTEGRITY_CHECK_FAILED.add_opt_mess("数据流头部不完整"));
+            }
+        }
+        let chunk_length = u32::from_be_bytes(chunk_length) as usize;
+        if chunk_length == 0 || chunk_length > STREAM_CRYPTER_MAX_CHUNK_LENGTH {
+            return Err(INVALID_STREAM_CHUNK_LENGTH.clone());
+        }
+        Ok((salt, iv, chunk_length))
+    }
+}
+
+impl<
+        CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait,
+        HR: BlockHasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > StreamCrypter<CA, HR, KD>
+{
+    /// 读取头部并根据密文的长度计算明文的长度和数据块的数量
+    /// 调用前src应位于密文的开头，返回时src位于密文的结尾
+    fn read_layout<R: Read + Seek>(src: &mut R) -> Result<StreamLayout> {
+        let (salt, iv, chunk_length) = Self::read_header(src)?;
+        let body_start = src
+            .stream_position()
+            .map_err(|e| FAILED_WHEN_READING_STREAM.add_opt_mess(&format!("{e}")))?;
+        let body_end = src
+            .seek(SeekFrom::End(0))
+            .map_err(|e| FAILED_WHEN_READING_STREAM.add_opt_mess(&format!("{e}")))?;
+        let body_length = body_end.saturating_sub(body_start);
+        let cipher_chunk_length = (HR::OUTPUT_LENGTH + chunk_length) as u64;
+        let full_chunk_count = body_length / cipher_chunk_length;
+        let rest = body_length % cipher_chunk_length;
+        // 除最后一块外每块都是完整的，最后一块至少包含校验值
+        let (plain_length, chunk_count) = if rest == 0 {
+            (full_chunk_count * chunk_length as u64, full_chunk_count)
+        } else if rest >= HR::OUTPUT_LENGTH as u64 {
+            (
+                full_chunk_count * chunk_length as u64 + rest - HR::OUTPUT_LENGTH as u64,
+                full_chunk_count + 1,
+            )
+        } else {
+            return Err(STREAM_INTEGRITY_CHECK_FAILED.add_opt_mess("数据块不完整"));
+        };
+        if chunk_count == 0 {
+            return Err(STREAM_INTEGRITY_CHECK_FAILED.add_opt_mess("数据流中没有数据块"));
+        }
+        Ok(StreamLayout {
+            m_salt: salt,
+            m_iv: iv,
+            m_chunk_length: chunk_length,
+            m_plain_length: plain_length,
+            m_chunk_count: chunk_count,
+        })
+    }
+
+    /// 根据密文的长度计算明文的长度，不需要密码
+    /// 调用前src应位于密文的开头
+    pub fn plaintext_length<R: Read + Seek>(src: &mut R) -> Result<u64> {
+        Ok(Self::read_layout(src)?.m_plain_length)
+    }
+
+    /// 只解密明文中从offset开始的len个字节并写入dst，返回写入的长度
+    /// 只读取并校验与该范围重叠的数据块以及最后一个数据块，超出明文结尾的部分会被截去
+    /// 调用前src应位于密文的开头
+    pub fn decrypt_range<R: Read + Seek>(
+        &self,
+        src: &mut R,
+        dst: &mut dyn Write,
+        password: &str,
+        offset: u64,
+        len: u64,
+    ) -> Result<u64> {
+        let start = src
+            .stream_position()
+            .map_err(|e| FAILED_WHEN_READING_STREAM.add_opt_mess(&format!("{e}")))?;
+        let layout = Self::read_layout(src)?;
+        let plain_length = layout.m_plain_length;
+        if offset > plain_length {
+            return Err(STREAM_RANGE_OUT_OF_BOUNDS
+                .add_opt_mess(&format!("起始位置{}超出了明文长度{}", offset, plain_length)));
+        }
+        let end = min(offset.saturating_add(len), plain_length);
+        if offset == end {
+            return Ok(0);
+        }
+        let (key, mut mac) = self.derive_keys(
+            password,
+            &layout.m_salt,
+            &layout.m_iv,
+            layout.m_chunk_length,
+        )?;
+        let mut stream_generator =
+            SeekableStreamGenerator::<CA>::new(&layout.m_iv, &key, StreamGeneratorMode::Medium)?;
+        let plain_chunk_length = layout.m_chunk_length as u64;
+        let cipher_chunk_length = (HR::OUTPUT_LENGTH + layout.m_chunk_length) as u64;
+        let body_start = start + Self::header_length() as u64;
+        let first_index = offset / plain_chunk_length;
+        let last_index = (end - 1) / plain_chunk_length;
+        let final_index = layout.m_chunk_count - 1;
+        // 先校验最后一个数据块以发现截断，再依次解密范围内的数据块
+        let tail_index = if last_index == final_index {
+            None
+        } else {
+            Some(final_index)
+        };
+        let mut chunk = vec![0u8; cipher_chunk_length as usize];
+        let mut next_index = None;
+        let mut total: u64 = 0;
+        for index in tail_index.into_iter().chain(first_index..=last_index) {
+            // 不连续时同时定位密文和密码流，每个数据块消耗的密码流长度与其明文长度相同
+            if next_index != Some(index) {
+                stream_generator.seek(index * plain_chunk_length)?;
+                src.seek(SeekFrom::Start(body_start + index * cipher_chunk_length))
+                    .map_err(|e| FAILED_WHEN_READING_STREAM.add_opt_mess(&format!("{e}")))?;
+            }
+            next_index = Some(index + 1);
+            let is_last = index == final_index;
+            let chunk_start = index * plain_chunk_length;
+            let current_len = if is_last {
+                HR::OUTPUT_LENGTH + (plain_length - chunk_start) as usize
+            } else {
+                cipher_chunk_length as usize
+            };
+            if read_full(src, &mut chunk[..current_len])? != current_len {
+                return Err(STREAM_INTEGRITY_CHECK_FAILED.add_opt_mess("数据块不完整"));
+            }
+            let plain_data_bytes = open_chunk(
+                &mut stream_generator,
+                &mut mac,
+                index,
+                is_last,
+                &chunk[..current_len],
+            )?;
+            if Some(index) == tail_index {
+                continue;
+            }
+            // 截取与请求范围重叠的部分
+            let from = (max(offset, chunk_start) - chunk_start) as usize;
+            let to = (min(end, chunk_start + plain_chunk_length) - chunk_start) as usize;
+            write_all(dst, &plain_data_bytes[from..to])?;
+            total += (to - from) as u64;
+        }
+        dst.flush()
+            .map_err(|e| FAILED_WHEN_WRITING_STREAM.add_opt_mess(&format!("{e}")))?;
+        Ok(total)
+    }
+}
+
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: BlockHasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > Default for StreamCrypter<CA, HR, KD>
+{
+    fn default() -> Self {
+        Self::with_kdf(KD::default())
+    }
+}
+
+impl<
+        CA: CipherAlgorithmTrait + IVKeyNewTrait,
+        HR: BlockHasherTrait + Default,
+        KD: KeyDerivationTrait + Default,
+    > StreamCrypterTrait for StreamCrypter<CA, HR, KD>
+{
+    fn encrypt(&self, src: &mut dyn Read, dst: &mut dyn Write, password: &str) -> Result<u64> {
+        // 创建一个密码流生成器
+        let salt = rand_iv(KD::SALT_LENGTH);
+        let iv = rand_iv(CA::IV_LENGTH);
+        let (key, mut mac) = self.derive_keys(password, &salt, &iv, self.m_chunk_length)?;
+        let mut stream_generator =
+            StreamGenerator::<CA>::new(&iv, &key, StreamGeneratorMode::Large)?;
+        // 写入头部
+        write_all(dst, &header_bytes(&salt, &iv, self.m_chunk_length))?;
+        // 需要预读下一块才能知道当前块是否为最后一块
+        let mut current = vec![0u8; self.m_chunk_length];
+        let mut next = vec![0u8; self.m_chunk_length];
+        let mut current_len = read_full(src, &mut current)?;
+        let mut index: u64 = 0;
+        let mut total: u64 = 0;
+        loop {
+            // 未读满一块说明已经到达结尾，无需再预读
+            let next_len = if current_len == self.m_chunk_length {
+                read_full(src, &mut next)?
+            } else {
+                0
+            };
+            let is_last = next_len == 0;
+            // 生成密码流进行加密，再计算密文的认证码
+            let cipher_data_bytes = {
+                let cipher_stream = stream_generator.generate(current_len)?;
+                xor(&cipher_stream, &current[..current_len])?
+            };
+            let tag = mac.tag(index, is_last, &cipher_data_bytes);
+            write_all(dst, &tag)?;
+            write_all(dst, &cipher_data_bytes)?;
+            total += current_len as u64;
+            if is_last {
+                break;
+            }
+            mem::swap(&mut current, &mut next);
+            current_len = next_len;
+            index += 1;
+        }
+        dst.flush()
+            .map_err(|e| FAILED_WHEN_WRITING_STREAM.add_opt_mess(&format!("{e}")))?;
+        Ok(total)
+    }
+
+    fn decrypt(&self, src: &mut dyn Read, dst: &mut dyn Write, password: &str) -> Result<u64> {
+        let (salt, iv, chunk_length) = Self::read_header(src)?;
+        // 创建一个密码流生成器
+        let (key, mut mac) = self.derive_keys(password, &salt, &iv, chunk_length)?;
+        let mut stream_generator =
+            StreamGenerator::<CA>::new(&iv, &key, StreamGeneratorMode::Large)?;
+        // 每个加密后的数据块由认证码和密文组成
+        let cipher_chunk_length = HR::OUTPUT_LENGTH + chunk_length;
+        let mut current = vec![0u8; cipher_chunk_length];
+        let mut next = vec![0u8; cipher_chunk_length];
+        let mut current_len = read_full(src, &mut current)?;
+        let mut index: u64 = 0;
+        let mut total: u64 = 0;
+        loop {
+            let next_len = if current_len == cipher_chunk_length {
+                read_full(src, &mut next)?
+            } else {
+                0
+            };
+            let is_last = next_len == 0;
+            let plain_data_bytes = open_chunk(
+                &mut stream_generator,
+                &mut mac,
+                index,
+                is_last,
+                &current[..current_len],
+            )?;
+            // 只写入通过校验的数据块
+            write_all(dst, &plain_data_bytes)?;
+            total += plain_data_bytes.len() as u64;
+            if is_last {
+                break;
+            }
+            mem::swap(&mut current, &mut next);
+            current_len = next_len;
+            index += 1;
+        }
+        dst.flush()
+            .map_err(|e| FAILED_WHEN_WRITING_STREAM.add_opt_mess(&format!("{e}")))?;
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::crypter::aes::{Aes128OfbCipherAlgorithm, Aes256CtrCipherAlgorithm};
+    use crate::crypter::chacha20::ChaCha20CipherAlgorithm;
+    use crate::crypter::kdf::Pbkdf2HmacSha256Kdf;
+    use crate::hash::sm3::Sm3Hasher;
+
+    fn test_data(len: usize) -> ByteVector {
+        (0..len).map(|i| (i * 7 + i / 251) as u8).collect()
+    }
+
+    fn encrypt_to_vec(stream_crypter: &dyn StreamCrypterTrait, data: &[u8]) -> ByteVector {
+        let mut res = ByteVector::new();
+        let len = stream_crypter
+            .encrypt(&mut Cursor::new(data), &mut res, "123456")
+            .unwrap();
+        assert_eq!(len, data.len() as u64);
+        res
+    }
+
+    fn decrypt_to_vec(
+        stream_crypter: &dyn StreamCrypterTrait,
+        data: &[u8],
+        password: &str,
+    ) -> Result<ByteVector> {
+        let mut res = ByteVector::new();
+        stream_crypter.decrypt(&mut Cursor::new(data), &mut res, password)?;
+        Ok(res)
+    }
+
+    #[test]
+    fn test_stream_crypter() {
+        let crypters: [Box<dyn StreamCrypterTrait>; 3] = [
+            Box::new(StreamCrypter::<ChaCha20CipherAlgorithm>::new(LegacySha256Kdf, 16).unwrap()),
+            Box::new(
+                StreamCrypter::<Aes128OfbCipherAlgorithm, Sm3Hasher>::new(LegacySha256Kdf, 17)
+                    .unwrap(),
+            ),
+            Box::new(
+                StreamCrypter::<ChaCha20CipherAlgorithm, Sha256Hasher, _>::new(
+                    Pbkdf2HmacSha256Kdf::new(1000).unwrap(),
+                    16,
+                )
+                .unwrap(),
+            ),
+        ];
+        for stream_crypter in crypters {
+            // 覆盖空数据、不足一块、恰好整块以及多块的情况
+            for len in [0, 1, 16, 17, 33, 100] {
+                let plain_data = test_data(len);
+                let cipher_data = encrypt_to_vec(stream_crypter.as_ref(), &plain_data);
+                assert_eq!(
+                    decrypt_to_vec(stream_crypter.as_ref(), &cipher_data, "123456").unwrap(),
+                    plain_data
+                );
+            }
+            // 错误的密码
+            let cipher_data = encrypt_to_vec(stream_crypter.as_ref(), &test_data(33));
+            let res = decrypt_to_vec(stream_crypter.as_ref(), &cipher_data, "654321");
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_stream_crypter_large() {
+        // 使用默认的数据块长度，数据长度跨越多个数据块
+        let stream_crypter = StreamCrypter::<ChaCha20CipherAlgorithm>::default();
+        let plain_data = test_data(STREAM_CRYPTER_DEFAULT_CHUNK_LENGTH * 3 + 12345);
+        let cipher_data = encrypt_to_vec(&stream_crypter, &plain_data);
+        assert_eq!(
+            decrypt_to_vec(&stream_crypter, &cipher_data, "123456").unwrap(),
+            plain_data
+        );
+    }
+
+    #[test]
+    fn test_stream_crypter_integrity() {
+        let stream_crypter =
+            StreamCrypter::<ChaCha20CipherAlgorithm>::new(LegacySha256Kdf, 4).unwrap();
+        // 头部16字节，之后为3个数据块，每块包含32字节的认证码和至多4字节的密文
+        let plain_data = test_data(10);
+        let cipher_data = encrypt_to_vec(&stream_crypter, &plain_data);
+        // 在任意位置截断都应该被发现
+        for i in 0..cipher_data.len() {
+            let res = decrypt_to_vec(&stream_crypter, &cipher_data[..i], "123456");
+            assert!(res.is_err(), "截断到{}字节时未发现", i);
+        }
+        let res = decrypt_to_vec(
+            &stream_crypter,
+            &cipher_data[..cipher_data.len() - 1],
+            "123456",
+        );
+        println!("错误：{}", res.unwrap_err());
+        // 修改iv、数据块长度、每个数据块的校验值和密文都应该被发现
+        for i in [0, 11, 13, 15, 16, 48, 52, 84, 88, 120, 121] {
+            let mut tampered = cipher_data.clone();
+            tampered[i] ^= 0x01;
+            let res = decrypt_to_vec(&stream_crypter, &tampered, "123456");
+            assert!(res.is_err(), "修改第{}字节时未发现", i);
+        }
+        // 在结尾追加内容也应该被发现
+        let appended = [&cipher_data[..], &[0x00; 8]].concat();
+        assert!(decrypt_to_vec(&stream_crypter, &appended, "123456").is_err());
+        // 不合法的数据块长度
+        assert!(StreamCrypter::<ChaCha20CipherAlgorithm>::new(LegacySha256Kdf, 0).is_err());
+    }
+
+    #[test]
+    fn test_stream_crypter_known_plaintext_forgery() {
+        // 知道明文但不知道密码的攻击者修改明文，并尝试修正认证码
+        let stream_crypter =
+            StreamCrypter::<ChaCha20CipherAlgorithm>::new(LegacySha256Kdf, 16).unwrap();
+        let plain_data = b"pay 100 to alice";
+        let forged_data = b"pay 999 to carol";
+        let cipher_data = encrypt_to_vec(&stream_crypter, plain_data);
+        let tag_start = StreamCrypter::<ChaCha20CipherAlgorithm>::header_length();
+        let data_start = tag_start + Sha256Hasher::OUTPUT_LENGTH;
+        // 不带密钥的哈希值，攻击者可以自行计算
+        let unkeyed = |data: &[u8]| {
+            let mut c = Sha256Hasher::default();
+            c.update_bytes(&0u64.to_be_bytes());
+            c.update_bytes(&[1]);
+            c.update_bytes(data);
+            c.finalize()
+        };
+        let mut tampered = cipher_data.clone();
+        for (i, (a, b)) in plain_data.iter().zip(forged_data).enumerate() {
+            tampered[data_start + i] ^= a ^ b;
+        }
+        let res = decrypt_to_vec(&stream_crypter, &tampered, "123456");
+        println!("错误：{}", res.unwrap_err());
+        // 按明文的哈希值之差修正认证码
+        let delta: ByteVector = unkeyed(plain_data)
+            .iter()
+            .zip(unkeyed(forged_data))
+            .map(|(a, b)| a ^ b)
+            .collect();
+        for (i, d) in delta.iter().enumerate() {
+            tampered[tag_start + i] ^= d;
+        }
+        assert!(decrypt_to_vec(&stream_crypter, &tampered, "123456").is_err());
+        // 用密文的哈希值替换认证码
+        let tag = unkeyed(&tampered[data_start..]);
+        tampered[tag_start..data_start].copy_from_slice(&tag);
+        assert!(decrypt_to_vec(&stream_crypter, &tampered, "123456").is_err());
+        // 未被修改的密文可以正常解密
+        assert_eq!(
+            decrypt_to_vec(&stream_crypter, &cipher_data, "123456").unwrap(),
+            plain_data
+        );
+    }
+
+    fn assert_decrypt_range<CA: SeekableCipherAlgorithmTrait + IVKeyNewTrait>() {
+        let stream_crypter = StreamCrypter::<CA>::new(LegacySha256Kdf, 16).unwrap();
+        for plain_length in [0, 1, 16, 40, 48] {
+            let plain_data = test_data(plain_length);
+            let cipher_data = encrypt_to_vec(&stream_crypter, &plain_data);
+            // 密文前可以有其他内容，解密从当前位置开始
+            let prefixed = [&[0xAA; 7][..], &cipher_data[..]].concat();
+            let mut src = Cursor::new(&prefixed[..]);
+            src.set_position(7);
+            assert_eq!(
+                StreamCrypter::<CA>::plaintext_length(&mut src).unwrap(),
+                plain_length as u64
+            );
+            // 覆盖数据块边界附近的起始位置和跨越多个数据块的长度
+            let offsets = [0, 1, 15, 16, 17, 33, plain_length];
+            for offset in offsets.into_iter().filter(|&offset| offset <= plain_length) {
+                for len in [0, 1, 5, 16, 17, 100] {
+                    let mut res = ByteVector::new();
+                    src.set_position(7);
+                    let n = stream_crypter
+                        .decrypt_range(&mut src, &mut res, "123456", offset as u64, len)
+                        .unwrap();
+                    let end = min(offset + len as usize, plain_length);
+                    assert_eq!(n, (end - offset) as u64);
+                    assert_eq!(res, plain_data[offset..end]);
+                }
+            }
+            // 起始位置超出明文长度
+            src.set_position(7);
+            let res = stream_crypter.decrypt_range(
+                &mut src,
+                &mut ByteVector::new(),
+                "123456",
+                plain_length as u64 + 1,
+                1,
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    #[test]
+    fn test_decrypt_range() {
+        assert_decrypt_range::<ChaCha20CipherAlgorithm>();
+        assert_decrypt_range::<Aes256CtrCipherAlgorithm>();
+        let stream_crypter =
+            StreamCrypter::<ChaCha20CipherAlgorithm>::new(LegacySha256Kdf, 16).unwrap();
+        let cipher_data = encrypt_to_vec(&stream_crypter, &test_data(40));
+        // 范围内的数据块被修改时无法通过校验，范围外的数据块不受影响
+        let mut tampered = cipher_data.clone();
+        // 第二个数据块的第一个字节
+        let second_chunk_start = StreamCrypter::<ChaCha20CipherAlgorithm>::header_length()
+            + Sha256Hasher::OUTPUT_LENGTH
+            + 16;
+        tampered[second_chunk_start] ^= 0x01;
+        let mut src = Cursor::new(&tampered[..]);
+        let res = stream_crypter.decrypt_range(&mut src, &mut ByteVector::new(), "123456", 16, 1);
+        println!("错误：{}", res.unwrap_err());
+        src.set_position(0);
+        let mut res = ByteVector::new();
+        stream_crypter
+            .decrypt_range(&mut src, &mut res, "123456", 0, 16)
+            .unwrap();
+        assert_eq!(res, test_data(40)[..16]);
+        // 错误的密码
+        let mut src = Cursor::new(&cipher_data[..]);
+        let res = stream_crypter.decrypt_range(&mut src, &mut ByteVector::new(), "654321", 20, 4);
+        assert!(res.is_err());
+        // 截断的密文
+        let mut src = Cursor::new(&cipher_data[..cipher_data.len() - 1]);
+        let res = stream_crypter.decrypt_range(&mut src, &mut ByteVector::new(), "123456", 0, 4);
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use crate::encoding::EncodingTrait;
+use crate::*;
+
+/// RFC 4648 标准字母表
+const STANDARD_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// RFC 4648 扩展hex字母表，编码后保持原数据的排序
+const HEX_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+/// Crockford's Base32字母表，去掉了容易混淆的I、L、O、U
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Crockford's Base32校验符号在字母表之外额外使用的5个符号，对应32~36
+const CROCKFORD_CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+/// z-base-32字母表，便于人工读写
+const Z_BASE32_ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+/// 填充字符
+const PADDING: u8 = b'=';
+/// 解码表中表示非法字符的值
+const INVALID_SYMBOL: u8 = 0xFF;
+/// 校验符号的模数
+const CHECK_MODULUS: u32 = 37;
+
+/// 生成忽略大小写的解码表
+const fn decode_table(alphabet: &[u8; 32]) -> [u8; 256] {
+    let mut table = [INVALID_SYMBOL; 256];
+    let mut i = 0;
+    while i < 32 {
+        table[alphabet[i].to_ascii_uppercase() as usize] = i as u8;
+        table[alphabet[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// Crockford's Base32解码时将O视为0，将I和L视为1
+const fn crockford_decode_table() -> [u8; 256] {
+    let mut table = decode_table(CROCKFORD_ALPHABET);
+    table[b'O' as usize] = 0;
+    table[b'o' as usize] = 0;
+    table[b'I' as usize] = 1;
+    table[b'i' as usize] = 1;
+    table[b'L' as usize] = 1;
+    table[b'l' as usize] = 1;
+    table
+}
+
+const STANDARD_DECODE_TABLE: [u8; 256] = decode_table(STANDARD_ALPHABET);
+const HEX_DECODE_TABLE: [u8; 256] = decode_table(HEX_ALPHABET);
+const CROCKFORD_DECODE_TABLE: [u8; 256] = crockford_decode_table();
+const Z_BASE32_DECODE_TABLE: [u8; 256] = decode_table(Z_BASE32_ALPHABET);
+
+/// Base32使用的字母表
+/// Standard和Hex可以选择是否填充，Crockford和z-base-32没有填充
+pub enum Base32Alphabet {
+    /// RFC 4648 标准字母表，如TOTP的密钥
+    Standard,
+    /// RFC 4648 扩展hex字母表（base32hex）
+    Hex,
+    /// Crockford's Base32，解码时忽略'-'
+    Crockford,
+    /// Crockford's Base32，末尾附加一个校验符号，校验值为数据按大端序视为整数后模37
+    CrockfordWithCheck,
+    /// z-base-32
+    ZBase32,
+}
+
+/// Base32编码，解码时忽略大小写
+/// 每5个字节编码为8个字符，不足5个字节时最后的字符中剩余的位补0
+pub struct Base32Encoding {
+    m_alphabet: Base32Alphabet,
+    m_padding: bool,
+}
+
+impl Base32Encoding {
+    /// padding为true时用'='将结果补齐到8的整数倍，解码时也要求有正确的填充
+    /// Crockford's Base32和z-base-32的规范中没有填充，会忽略该参数
+    pub const fn new(alphabet: Base32Alphabet, padding: bool) -> Base32Encoding {
+        Base32Encoding {
+            m_alphabet: alphabet,
+            m_padding: padding,
+        }
+    }
+
+    fn alphabet(&self) -> &'static [u8; 32] {
+        match self.m_alphabet {
+            Base32Alphabet::Standard => STANDARD_ALPHABET,
+            Base32Alphabet::Hex => HEX_ALPHABET,
+            Base32Alphabet::Crockford | Base32Alphabet::CrockfordWithCheck => CROCKFORD_ALPHABET,
+            Base32Alphabet::ZBase32 => Z_BASE32_ALPHABET,
+        }
+    }
+
+    fn decode_table(&self) -> &'static [u8; 256] {
+        match self.m_alphabet {
+            Base32Alphabet::Standard => &STANDARD_DECODE_TABLE,
+            Base32Alphabet::Hex => &HEX_DECODE_TABLE,
+            Base32Alphabet::Crockford | Base32Alphabet::CrockfordWithCheck => {
+                &CROCKFORD_DECODE_TABLE
+            }
+            Base32Alphabet::ZBase32 => &Z_BASE32_DECODE_TABLE,
+        }
+    }
+
+    fn is_padded(&self) -> bool {
+        self.m_padding
+            && matches!(
+                self.m_alphabet,
+                Base32Alphabet::Standard | Base32Alphabet::Hex
+            )
+    }
+
+    fn has_check_symbol(&self) -> bool {
+        matches!(self.m_alphabet, Base32Alphabet::CrockfordWithCheck)
+    }
+
+    /// 将数据按大端序视为一个整数，计算其模37的值
+    fn check_value(data: &[u8]) -> u32 {
+        data.iter()
+            .fold(0, |r, &b| (r * 256 + b as u32) % CHECK_MODULUS)
+    }
+}
+
+impl Default for Base32Encoding {
+    fn default() -> Self {
+        Self::new(Base32Alphabet::Standard, true)
+    }
+}
+
+impl EncodingTrait for Base32Encoding {
+    fn encode(&self, data: &[u8]) -> String {
+        let alphabet = self.alphabet();
+        let mut symbols = ByteVector::with_capacity(data.len().div_ceil(5) * 8);
+        let mut bits: u16 = 0;
+        let mut bit_count = 0;
+        for &b in data {
+            bits = (bits << 8) | b as u16;
+            bit_count += 8;
+            while bit_count >= 5 {
+                bit_count -= 5;
+                symbols.push(((bits >> bit_count) & 0x1F) as u8);
+            }
+            bits &= (1 << bit_count) - 1;
+        }
+        if bit_count > 0 {
+            symbols.push(((bits << (5 - bit_count)) & 0x1F) as u8);
+        }
+        let mut res: String = symbols
+            .iter()
+            .map(|&v| alphabet[v as usize] as char)
+            .collect();
+        if self.is_padded() {
+            while !res.len().is_multiple_of(8) {
+                res.push(PADDING as char);
+            }
+        }
+        if self.has_check_symbol() {
+            let check = Self::check_value(data) as usize;
+            res.push(if check < 32 {
+                alphabet[check]
+            } else {
+                CROCKFORD_CHECK_SYMBOLS[check - 32]
+            } as char);
+        }
+        res
+    }
+
+    fn decode(&self, data: &str) -> Result<ByteVector> {
+        let mut data = data.as_bytes().to_vec();
+        if matches!(
+            self.m_alphabet,
+            Base32Alphabet::Crockford | Base32Alphabet::CrockfordWithCheck
+        ) {
+            data.retain(|&c| c != b'-');
+        }
+        // 校验符号
+        let mut check = None;
+        if self.has_check_symbol() {
+            let c = data
+                .pop()
+                .ok_or(BASE32_BAD_DECODING_SOURCE.add_opt_mess("缺少校验符号"))?;
+            let value = match CROCKFORD_CHECK_SYMBOLS
+                .iter()
+                .position(|&s| s == c.to_ascii_uppercase())
+            {
+                Some(i) => i as u8 + 32,
+                None => CROCKFORD_DECODE_TABLE[c as usize],
+            };
+            if value == INVALID_SYMBOL {
+                return Err(BASE32_BAD_DECODING_SOURCE
+                    .add_opt_mess(&format!("非法的校验符号'{}'", c as char)));
+            }
+            check = Some(value as u32);
+        }
+        // 填充
+        if self.is_padded() {
+            if !data.len().is_multiple_of(8) {
+                return Err(BASE32_BAD_DECODING_SOURCE.add_opt_mess("填充后的长度不是8的整数倍"));
+            }
+            // 最多有6个填充字符
+            let mut padding_count = 0;
+            while padding_count < 6 && data.last() == Some(&PADDING) {
+                data.pop();
+                padding_count += 1;
+            }
+        }
+        if !matches!(data.len() % 8, 0 | 2 | 4 | 5 | 7) {
+            return Err(
+                BASE32_BAD_DECODING_SOURCE.add_opt_mess(&format!("不合法的长度{}", data.len()))
+            );
+        }
+        // 字符转换为对应的值
+        let table = self.decode_table();
+        let mut symbols = ByteVector::with_capacity(data.len());
+        for (i, &c) in data.iter().enumerate() {
+            let value = table[c as usize];
+            if value == INVALID_SYMBOL {
+                return Err(BASE32_BAD_DECODING_SOURCE.add_opt_mess(&format!(
+                    "第{}个字符'{}'不合法",
+                    i + 1,
+                    c as char
+                )));
+            }
+            symbols.push(value);
+        }
+        let mut res = ByteVector::with_capacity(symbols.len() * 5 / 8);
+        let mut bits: u16 = 0;
+        let mut bit_count = 0;
+        for &v in &symbols {
+            bits = (bits << 5) | v as u16;
+            bit_count += 5;
+            if bit_count >= 8 {
+                bit_count -= 8;
+                res.push((bits >> bit_count) as u8);
+                bits &= (1 << bit_count) - 1;
+            }
+        }
+        // 补的位必须为0，否则同一个数据会有多种编码
+        if bits != 0 {
+            return Err(BASE32_BAD_DECODING_SOURCE.add_opt_mess("最后一个字符中补的位不为0"));
+        }
+        if let Some(check) = check {
+            if check != Self::check_value(&res) {
+                return Err(BASE32_CHECK_SYMBOL_MISMATCH.clone());
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32_rfc4648() {
+        // RFC 4648 第10节的测试向量
+        let vectors: [(&str, &str, &str); 7] = [
+            ("", "", ""),
+            ("f", "MY======", "CO======"),
+            ("fo", "MZXQ====", "CPNG===="),
+            ("foo", "MZXW6===", "CPNMU==="),
+            ("foob", "MZXW6YQ=", "CPNMUOG="),
+            ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+            ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+        ];
+        let b32 = Base32Encoding::default();
+        let b32_no_pad = Base32Encoding::new(Base32Alphabet::Standard, false);
+        let b32hex = Base32Encoding::new(Base32Alphabet::Hex, true);
+        for (data, standard, hex) in vectors {
+            assert_eq!(b32.encode(data.as_bytes()), standard);
+            assert_eq!(b32.decode(standard).unwrap(), data.as_bytes());
+            assert_eq!(b32hex.encode(data.as_bytes()), hex);
+            assert_eq!(b32hex.decode(hex).unwrap(), data.as_bytes());
+            let unpadded = standard.trim_end_matches('=');
+            assert_eq!(b32_no_pad.encode(data.as_bytes()), unpadded);
+            assert_eq!(b32_no_pad.decode(unpadded).unwrap(), data.as_bytes());
+        }
+        // 忽略大小写，如小写的TOTP密钥
+        assert_eq!(b32.decode("mzxw6ytboi======").unwrap(), b"foobar");
+        // 不合法的输入
+        for s in [
+            "MZXW6YTBOI",
+            "MZXW6YTB1I======",
+            "MZXW6===MZXW6===",
+            "MZ=W6YTB",
+        ] {
+            let res = b32.decode(s);
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+        }
+        for s in ["MZXW6YQ=", "MZXW6YTBO"] {
+            let res = b32_no_pad.decode(s);
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+        }
+        // 补的位不为0
+        assert!(b32_no_pad.decode("MZ").is_err());
+        assert!(b32_no_pad.decode("MY").is_ok());
+    }
+
+    #[test]
+    fn test_base32_crockford() {
+        let crockford = Base32Encoding::new(Base32Alphabet::Crockford, true);
+        let res = crockford.encode("Hello, ciftl!".as_bytes());
+        assert_eq!(res, "91JPRV3F5GG66TB6EHP22");
+        assert_eq!(crockford.decode(&res).unwrap(), "Hello, ciftl!".as_bytes());
+        // 忽略大小写和'-'，O视为0，I和L视为1
+        assert_eq!(
+            crockford.decode("91jprv3f-5gg66tb6-ehp22").unwrap(),
+            "Hello, ciftl!".as_bytes()
+        );
+        assert_eq!(
+            crockford.decode("CSQPYRKIE8").unwrap(),
+            crockford.decode("CSQPYRK1E8").unwrap()
+        );
+        assert_eq!(
+            crockford.decode("0O").unwrap(),
+            crockford.decode("00").unwrap()
+        );
+        let res = crockford.decode("CSQPYRKUE8");
+        assert!(res.is_err());
+        println!("错误：{}", res.unwrap_err());
+        // 校验符号
+        let crockford = Base32Encoding::new(Base32Alphabet::CrockfordWithCheck, false);
+        let vectors: [(&[u8], &str); 5] = [
+            (b"", "0"),
+            // 102模37为28
+            (b"f", "CRW"),
+            (b"foobar", "CSQPYRK1E86"),
+            (b"Hello, ciftl!", "91JPRV3F5GG66TB6EHP22K"),
+            (&[0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF], "ZBXZSZFYZWM"),
+        ];
+        for (data, encoded) in vectors {
+            assert_eq!(crockford.encode(data), encoded);
+            assert_eq!(crockford.decode(encoded).unwrap(), data);
+        }
+        assert_eq!(crockford.decode("csqp-yrk1-e86").unwrap(), b"foobar");
+        // 校验符号或数据被修改
+        for s in ["CSQPYRK1E8S", "CSQPYRK1F86"] {
+            let res = crockford.decode(s);
+            assert_eq!(
+                format!("{}", res.unwrap_err()),
+                format!("{}", BASE32_CHECK_SYMBOL_MISMATCH)
+            );
+        }
+        for s in ["", "CSQPYRK1E8#"] {
+            let res = crockford.decode(s);
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_z_base32() {
+        let zb32 = Base32Encoding::new(Base32Alphabet::ZBase32, false);
+        // z-base-32规范中的示例
+        assert_eq!(zb32.encode(&[0xF0, 0xBF, 0xC7]), "6n9hq");
+        assert_eq!(zb32.encode(&[0xD4, 0x7A, 0x04]), "4t7ye");
+        assert_eq!(zb32.encode(b"foobar"), "c3zs6aubqe");
+        assert_eq!(zb32.decode("c3zs6aubqe").unwrap(), b"foobar");
+        assert_eq!(zb32.decode("C3ZS6AUBQE").unwrap(), b"foobar");
+        let data: ByteVector = (0..=255).collect();
+        assert_eq!(zb32.decode(&zb32.encode(&data)).unwrap(), data);
+        let res = zb32.decode("c3zs6aubqv");
+        assert!(res.is_err());
+        println!("错误：{}", res.unwrap_err());
+    }
+}
@@ -0,0 +1,320 @@
+use crate::encoding::EncodingTrait;
+use crate::hash::sha256::{Sha256Hasher, SHA256_OUTPUT_LENGTH};
+use crate::hash::{HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// 比特币使用的字母表，去掉了容易混淆的0、O、I、l
+const BITCOIN_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// 瑞波币使用的字母表
+const RIPPLE_ALPHABET: &[u8; 58] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+/// Flickr短链接使用的字母表
+const FLICKR_ALPHABET: &[u8; 58] = b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+/// 解码表中表示非法字符的值
+const INVALID_SYMBOL: u8 = 0xFF;
+/// Base58Check的校验和长度
+pub const BASE58_CHECKSUM_LENGTH: usize = 4;
+/// 解码时允许的最大输入长度，Base58的编解码耗时与长度的平方成正比，过长的输入会被拒绝
+pub const BASE58_MAX_DECODING_LENGTH: usize = 4096;
+/// 编码时允许的最大数据长度（不含校验和），保证编码的结果不超过BASE58_MAX_DECODING_LENGTH
+pub const BASE58_MAX_ENCODING_LENGTH: usize = 2990;
+
+/// 生成解码表，Base58区分大小写
+const fn decode_table(alphabet: &[u8; 58]) -> [u8; 256] {
+    let mut table = [INVALID_SYMBOL; 256];
+    let mut i = 0;
+    while i < 58 {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+const BITCOIN_DECODE_TABLE: [u8; 256] = decode_table(BITCOIN_ALPHABET);
+const RIPPLE_DECODE_TABLE: [u8; 256] = decode_table(RIPPLE_ALPHABET);
+const FLICKR_DECODE_TABLE: [u8; 256] = decode_table(FLICKR_ALPHABET);
+
+/// Base58使用的字母表，各字母表包含的字符相同，只是顺序不同
+pub enum Base58Alphabet {
+    /// 比特币地址、IPFS等使用的字母表
+    Bitcoin,
+    /// 瑞波币地址使用的字母表
+    Ripple,
+    /// Flickr短链接使用的字母表
+    Flickr,
+}
+
+/// Base58编码，将数据视为一个大端序的大整数转换为58进制
+/// 开头的每个0x00编码为字母表中的第一个字符
+pub struct Base58Encoding {
+    m_alphabet: Base58Alphabet,
+    m_check: bool,
+}
+
+impl Base58Encoding {
+    /// 不带校验和的Base58，数据按原样编码
+    pub const fn new(alphabet: Base58Alphabet) -> Base58Encoding {
+        Base58Encoding {
+            m_alphabet: alphabet,
+            m_check: false,
+        }
+    }
+
+    /// Base58Check，编码前在数据末尾附加两次SHA256的结果的前4个字节作为校验和
+    /// 解码时验证并去除校验和，版本号等前缀由调用者放在数据的开头
+    pub const fn new_check(alphabet: Base58Alphabet) -> Base58Encoding {
+        Base58Encoding {
+            m_alphabet: alphabet,
+            m_check: true,
+        }
+    }
+
+    fn alphabet(&self) -> &'static [u8; 58] {
+        match self.m_alphabet {
+            Base58Alphabet::Bitcoin => BITCOIN_ALPHABET,
+            Base58Alphabet::Ripple => RIPPLE_ALPHABET,
+            Base58Alphabet::Flickr => FLICKR_ALPHABET,
+        }
+    }
+
+    fn decode_table(&self) -> &'static [u8; 256] {
+        match self.m_alphabet {
+            Base58Alphabet::Bitcoin => &BITCOIN_DECODE_TABLE,
+            Base58Alphabet::Ripple => &RIPPLE_DECODE_TABLE,
+            Base58Alphabet::Flickr => &FLICKR_DECODE_TABLE,
+        }
+    }
+
+    /// 两次SHA256的结果的前4个字节
+    fn checksum(data: &[u8]) -> ByteArray<BASE58_CHECKSUM_LENGTH> {
+        let mut hasher = Sha256Hasher::default();
+        hasher.update_bytes(data);
+        let digest = hasher.finalize_array_reset::<SHA256_OUTPUT_LENGTH>();
+        hasher.update_bytes(&digest);
+        let digest = hasher.finalize_array::<SHA256_OUTPUT_LENGTH>();
+        let mut res = ByteArray::<BASE58_CHECKSUM_LENGTH>::new();
+        res.copy_from_slice(&digest.as_ref()[..BASE58_CHECKSUM_LENGTH]);
+        res
+    }
+
+    /// 编码，数据长度超过BASE58_MAX_ENCODING_LENGTH时返回BASE58_BAD_LENGTH
+    pub fn try_encode(&self, data: &[u8]) -> Result<String> {
+        if data.len() > BASE58_MAX_ENCODING_LENGTH {
+            return Err(BASE58_BAD_LENGTH.add_opt_mess(&format!(
+                "数据长度为{}，超过了上限{}",
+                data.len(),
+                BASE58_MAX_ENCODING_LENGTH
+            )));
+        }
+        let mut data = data.to_vec();
+        if self.m_check {
+            let checksum = Self::checksum(&data);
+            data.extend_from_slice(&checksum);
+        }
+        let alphabet = self.alphabet();
+        let zero_count = data.iter().take_while(|&&b| b == 0x00).count();
+        // 58进制的各位，低位在前，长度约为log(256)/log(58)≈1.37倍
+        let mut digits = ByteVector::with_capacity(data.len() * 138 / 100 + 1);
+        for &b in &data[zero_count..] {
+            let mut carry = b as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+        let mut res = String::with_capacity(zero_count + digits.len());
+        for _ in 0..zero_count {
+            res.push(alphabet[0] as char);
+        }
+        for &digit in digits.iter().rev() {
+            res.push(alphabet[digit as usize] as char);
+        }
+        Ok(res)
+    }
+}
+
+impl Default for Base58Encoding {
+    fn default() -> Self {
+        Self::new(Base58Alphabet::Bitcoin)
+    }
+}
+
+impl EncodingTrait for Base58Encoding {
+    /// 数据长度超过BASE58_MAX_ENCODING_LENGTH时会panic，需要处理过长的数据时使用try_encode
+    fn encode(&self, data: &[u8]) -> String {
+        match self.try_encode(data) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn decode(&self, data: &str) -> Result<ByteVector> {
+        let table = self.decode_table();
+        let zero_symbol = self.alphabet()[0];
+        let data = data.as_bytes();
+        if data.len() > BASE58_MAX_DECODING_LENGTH {
+            return Err(BASE58_BAD_LENGTH.add_opt_mess(&format!(
+                "长度为{}，超过了上限{}",
+                data.len(),
+                BASE58_MAX_DECODING_LENGTH
+            )));
+        }
+        let zero_count = data.iter().take_while(|&&c| c == zero_symbol).count();
+        // 256进制的各位，低位在前
+        let mut bytes = ByteVector::with_capacity(data.len() * 733 / 1000 + 1);
+        for (i, &c) in data.iter().enumerate().skip(zero_count) {
+            let value = table[c as usize];
+            if value == INVALID_SYMBOL {
+                return Err(BASE58_BAD_DECODING_SOURCE.add_opt_mess(&format!(
+                    "第{}个字符'{}'不合法",
+                    i + 1,
+                    c as char
+                )));
+            }
+            let mut carry = value as u32;
+            for byte in bytes.iter_mut() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+        let mut res = vec![0x00; zero_count];
+        res.extend(bytes.iter().rev());
+        if self.m_check {
+            if res.len() < BASE58_CHECKSUM_LENGTH {
+                return Err(BASE58_BAD_LENGTH.add_opt_mess("长度不足以包含校验和"));
+            }
+            let payload_length = res.len() - BASE58_CHECKSUM_LENGTH;
+            if Self::checksum(&res[..payload_length]).as_ref() != &res[payload_length..] {
+                return Err(BASE58_CHECKSUM_MISMATCH.clone());
+            }
+            res.truncate(payload_length);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+
+    #[test]
+    fn test_base58() {
+        let hexe = HexEncoding::default();
+        let vectors: [(&str, &str, &str, &str); 4] = [
+            ("", "", "", ""),
+            ("00", "1", "r", "1"),
+            (
+                "48656C6C6F20576F726C6421",
+                "2NEpo7TZRRrLZSi2U",
+                "p4NFofTZRRiLZS5p7",
+                "2nePN7syqqRkyrH2t",
+            ),
+            ("0000287FB4CD", "11233QC4", "rrpssQUh", "11233pc4"),
+        ];
+        let bitcoin = Base58Encoding::default();
+        let ripple = Base58Encoding::new(Base58Alphabet::Ripple);
+        let flickr = Base58Encoding::new(Base58Alphabet::Flickr);
+        for (data, b, r, f) in vectors {
+            let data = hexe.decode(data).unwrap();
+            assert_eq!(bitcoin.encode(&data), b);
+            assert_eq!(bitcoin.decode(b).unwrap(), data);
+            assert_eq!(ripple.encode(&data), r);
+            assert_eq!(ripple.decode(r).unwrap(), data);
+            assert_eq!(flickr.encode(&data), f);
+            assert_eq!(flickr.decode(f).unwrap(), data);
+        }
+        let data: ByteVector = (0..=255).collect();
+        assert_eq!(bitcoin.decode(&bitcoin.encode(&data)).unwrap(), data);
+        // 不合法的字符
+        for s in [
+            "2NEpo7TZRRrLZSi20",
+            "2NEpo7TZRRrLZSiIU",
+            "2NEpo7TZ RRrLZSi2U",
+            "你好",
+        ] {
+            let res = bitcoin.decode(s);
+            assert!(res.is_err());
+            println!("错误：{}", res.unwrap_err());
+        }
+        // 超过长度上限
+        let s = "2".repeat(BASE58_MAX_DECODING_LENGTH);
+        assert!(bitcoin.decode(&s).is_ok());
+        let s = "2".repeat(BASE58_MAX_DECODING_LENGTH + 1);
+        let res = bitcoin.decode(&s);
+        let err = format!("{}", res.unwrap_err());
+        println!("错误：{}", err);
+        assert!(err.starts_with(&format!("{}", BASE58_BAD_LENGTH)));
+        // 编码的长度上限，上限内的数据编码后总是可以解码
+        let check = Base58Encoding::new_check(Base58Alphabet::Bitcoin);
+        let data = vec![0xFF; BASE58_MAX_ENCODING_LENGTH];
+        let res = check.try_encode(&data).unwrap();
+        assert!(res.len() <= BASE58_MAX_DECODING_LENGTH);
+        assert_eq!(check.decode(&res).unwrap(), data);
+        let data = vec![0xFF; BASE58_MAX_ENCODING_LENGTH + 1];
+        for encoding in [&bitcoin, &check] {
+            let err = format!("{}", encoding.try_encode(&data).unwrap_err());
+            println!("错误：{}", err);
+            assert!(err.starts_with(&format!("{}", BASE58_BAD_LENGTH)));
+        }
+    }
+
+    #[test]
+    fn test_base58_check() {
+        let hexe = HexEncoding::default();
+        // 比特币P2PKH地址，版本号0x00
+        let bitcoin = Base58Encoding::new_check(Base58Alphabet::Bitcoin);
+        let payload = hexe
+            .decode("00010966776006953D5567439E5E39F86A0D273BEE")
+            .unwrap();
+        assert_eq!(
+            bitcoin.encode(&payload),
+            "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM"
+        );
+        assert_eq!(
+            bitcoin.decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM").unwrap(),
+            payload
+        );
+        // 瑞波币的零账户
+        let ripple = Base58Encoding::new_check(Base58Alphabet::Ripple);
+        assert_eq!(ripple.encode(&[0x00; 21]), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+        assert_eq!(
+            ripple.decode("rrrrrrrrrrrrrrrrrrrrrhoLvTp").unwrap(),
+            [0x00; 21]
+        );
+        assert_eq!(bitcoin.encode(b""), "3QJmnh");
+        assert_eq!(bitcoin.decode("3QJmnh").unwrap(), b"");
+        let flickr = Base58Encoding::new_check(Base58Alphabet::Flickr);
+        assert_eq!(flickr.encode(b"Hello World!"), "9WvseMnstZigd7BwZ99DJx");
+        // 校验和不匹配
+        for s in [
+            "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN",
+            "17UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM",
+        ] {
+            let res = bitcoin.decode(s);
+            assert_eq!(
+                format!("{}", res.unwrap_err()),
+                format!("{}", BASE58_CHECKSUM_MISMATCH)
+            );
+        }
+        // 不合法的字符
+        let res = bitcoin.decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjv0");
+        assert!(res.is_err());
+        println!("错误：{}", res.unwrap_err());
+        // 长度不足以包含校验和
+        for s in ["", "1", "111"] {
+            let err = format!("{}", bitcoin.decode(s).unwrap_err());
+            println!("错误：{}", err);
+            assert!(err.starts_with(&format!("{}", BASE58_BAD_LENGTH)));
+        }
+    }
+}
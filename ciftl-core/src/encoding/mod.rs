@@ -1,5 +1,7 @@
 pub mod hex;
 pub mod base64;
+pub mod base32;
+pub mod base58;
 
 use crate::*;
 
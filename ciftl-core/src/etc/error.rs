@@ -13,6 +13,8 @@ pub enum ErrorCodeEnum {
     EncodingError = ErrorCodeEnum::ErrorCodeBase as isize + 2 * 1000,
     /// 加密器错误段
     CrypterError = ErrorCodeEnum::ErrorCodeBase as isize + 3 * 1000,
+    /// 哈希错误段
+    HashError = ErrorCodeEnum::ErrorCodeBase as isize + 4 * 1000,
 }
 
 #[derive(FromPrimitive)]
@@ -25,6 +27,20 @@ pub enum EncodingErrorCodeEnum {
     /// Base64的错误段
     Base64EncodingError = ErrorCodeEnum::EncodingError as isize + 3 * 100,
     Base64BadDecodingSource,
+    /// Base32的错误段
+    Base32EncodingError = ErrorCodeEnum::EncodingError as isize + 4 * 100,
+    /// 非法的Base32字符串
+    Base32BadDecodingSource,
+    /// Crockford Base32的校验符号不匹配
+    Base32CheckSymbolMismatch,
+    /// Base58的错误段
+    Base58EncodingError = ErrorCodeEnum::EncodingError as isize + 5 * 100,
+    /// 非法的Base58字符串
+    Base58BadDecodingSource,
+    /// Base58Check的校验和不匹配
+    Base58ChecksumMismatch,
+    /// Base58的数据或字符串的长度不合法
+    Base58BadLength,
 }
 
 #[derive(FromPrimitive)]
@@ -35,6 +51,10 @@ pub enum CrypterErrorCodeEnum {
     CipherAlgorithmUnsatisfiedIVLength,
     /// 不满足要求的Key长度
     CipherAlgorithmUnsatisfiedKeyLength,
+    /// 数据长度不是分组长度的整数倍
+    CipherAlgorithmUnsatisfiedDataLength,
+    /// 未知的密码算法
+    UnknownCipherAlgorithm,
     /// 流生成器错误段
     StreamGeneratorError = ErrorCodeEnum::CrypterError as isize + 2 * 100,
     /// 执行密码操作时失败
@@ -43,10 +63,22 @@ pub enum CrypterErrorCodeEnum {
     FailedWhenFinalizingCryption,
     /// 刷新缓冲区时失败
     FailedWhenFlushingBuffer,
+    /// 定位密码流时失败
+    FailedWhenSeekingStream,
     /// 流加密器错误段
     StreamCrypterError = ErrorCodeEnum::CrypterError as isize + 3 * 100,
     /// 刷新时当前下标不在缓冲区的最后
     CurrentIndexNotAtTheEndOfBufferWhenFlushing,
+    /// 读取数据流时失败
+    FailedWhenReadingStream,
+    /// 写入数据流时失败
+    FailedWhenWritingStream,
+    /// 数据流完整性校验失败，内容可能被截断或篡改
+    StreamIntegrityCheckFailed,
+    /// 不合法的数据块长度
+    InvalidStreamChunkLength,
+    /// 请求的范围超出了数据流的长度
+    StreamRangeOutOfBounds,
     /// 字符串加密器错误段
     StringCrypterError = ErrorCodeEnum::CrypterError as isize + 4 * 100,
     /// 解密后内容无法通过校验
@@ -57,6 +89,34 @@ pub enum CrypterErrorCodeEnum {
     FailedWhenDecodingString,
     /// 不能对空串加密
     CannotEncryptEmptyString,
+    /// 分组加密器错误段
+    BlockCrypterError = ErrorCodeEnum::CrypterError as isize + 5 * 100,
+    /// 去除填充时失败，填充内容不合法
+    FailedWhenUnpadding,
+    /// 分组加密器只能使用分组密码算法
+    BlockCrypterRequiresBlockCipherAlgorithm,
+    /// 认证加密器错误段
+    AeadCrypterError = ErrorCodeEnum::CrypterError as isize + 6 * 100,
+    /// 认证加密时失败
+    FailedWhenAeadEncrypting,
+    /// 密文认证失败，内容可能被篡改
+    FailedWhenAuthenticatingCiphertext,
+    /// 密钥派生错误段
+    KeyDerivationError = ErrorCodeEnum::CrypterError as isize + 7 * 100,
+    /// 不合法的密钥派生参数
+    InvalidKdfParameters,
+    /// 派生密钥时失败
+    FailedWhenDerivingKey,
+    /// 密文头部错误段
+    CiphertextHeaderError = ErrorCodeEnum::CrypterError as isize + 8 * 100,
+    /// 密文头部的魔数不匹配
+    BadCiphertextMagic,
+    /// 不支持的密文版本
+    UnsupportedCiphertextVersion,
+    /// 未知的算法标识
+    UnknownAlgorithmIdentifier,
+    /// 不兼容的算法组合
+    IncompatibleAlgorithmCombination,
 }
 
 #[derive(FromPrimitive)]
@@ -71,6 +131,28 @@ pub enum EtcErrorCodeEnum {
     MemoryTakerHasNoEnoughContent,
 }
 
+#[derive(FromPrimitive)]
+pub enum HashErrorCodeEnum {
+    /// 哈希操作的错误段
+    HasherError = ErrorCodeEnum::HashError as isize + 1 * 100,
+    /// 不满足要求的哈希密钥长度
+    HasherUnsatisfiedKeyLength,
+    /// 不合法的CRC参数
+    InvalidCrcParameters,
+    /// 未知的哈希算法
+    UnknownHashAlgorithm,
+    /// 当前构建中未启用的哈希算法
+    HashAlgorithmNotEnabled,
+    /// 读取待哈希的数据失败
+    FailedWhenReadingHashInput,
+    /// 不合法的校验和行
+    InvalidChecksumLine,
+    /// 不合法的默克尔树数据块长度
+    InvalidMerkleChunkLength,
+    /// 默克尔树叶子节点的序号超出范围
+    MerkleLeafIndexOutOfRange,
+}
+
 /// 错误码
 pub type ErrorCode = u32;
 
@@ -159,6 +241,31 @@ pub mod predef {
         EncodingErrorCodeEnum::Base64BadDecodingSource as ErrorCode,
         "非法的Base64字符串",
     );
+    // 12401
+    pub const BASE32_BAD_DECODING_SOURCE: &'static CiftlError = &CiftlError::new(
+        EncodingErrorCodeEnum::Base32BadDecodingSource as ErrorCode,
+        "非法的Base32字符串",
+    );
+    // 12402
+    pub const BASE32_CHECK_SYMBOL_MISMATCH: &'static CiftlError = &CiftlError::new(
+        EncodingErrorCodeEnum::Base32CheckSymbolMismatch as ErrorCode,
+        "Base32的校验符号不匹配",
+    );
+    // 12501
+    pub const BASE58_BAD_DECODING_SOURCE: &'static CiftlError = &CiftlError::new(
+        EncodingErrorCodeEnum::Base58BadDecodingSource as ErrorCode,
+        "非法的Base58字符串",
+    );
+    // 12502
+    pub const BASE58_CHECKSUM_MISMATCH: &'static CiftlError = &CiftlError::new(
+        EncodingErrorCodeEnum::Base58ChecksumMismatch as ErrorCode,
+        "Base58Check的校验和不匹配",
+    );
+    // 12503
+    pub const BASE58_BAD_LENGTH: &'static CiftlError = &CiftlError::new(
+        EncodingErrorCodeEnum::Base58BadLength as ErrorCode,
+        "Base58的长度不合法",
+    );
 
     // 13101
     pub const CIPHER_ALGORITHM_UNSATISFIED_IV_LENGTH: &'static CiftlError = &CiftlError::new(
@@ -172,12 +279,30 @@ pub mod predef {
         "不满足要求的Key长度",
     );
 
+    // 13103
+    pub const CIPHER_ALGORITHM_UNSATISFIED_DATA_LENGTH: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::CipherAlgorithmUnsatisfiedDataLength as ErrorCode,
+        "数据长度不是分组长度的整数倍",
+    );
+
+    // 13104
+    pub const UNKNOWN_CIPHER_ALGORITHM: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::UnknownCipherAlgorithm as ErrorCode,
+        "未知的密码算法",
+    );
+
     // 13203
     pub const FAILED_WHEN_FLUSHING_BUFFER: &'static CiftlError = &CiftlError::new(
         CrypterErrorCodeEnum::FailedWhenFlushingBuffer as ErrorCode,
         "刷新缓冲区时失败",
     );
 
+    // 13204
+    pub const FAILED_WHEN_SEEKING_STREAM: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenSeekingStream as ErrorCode,
+        "定位密码流时失败",
+    );
+
     // 13301
     pub const CURRENT_INDEX_NOT_AT_THE_END_OF_BUFFER_WHEN_FLUSHING: &'static CiftlError =
         &CiftlError::new(
@@ -185,6 +310,36 @@ pub mod predef {
             "刷新时当前下标不在缓冲区的最后",
         );
 
+    // 13302
+    pub const FAILED_WHEN_READING_STREAM: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenReadingStream as ErrorCode,
+        "读取数据流时失败",
+    );
+
+    // 13303
+    pub const FAILED_WHEN_WRITING_STREAM: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenWritingStream as ErrorCode,
+        "写入数据流时失败",
+    );
+
+    // 13304
+    pub const STREAM_INTEGRITY_CHECK_FAILED: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::StreamIntegrityCheckFailed as ErrorCode,
+        "数据流完整性校验失败，内容可能被截断或篡改",
+    );
+
+    // 13305
+    pub const INVALID_STREAM_CHUNK_LENGTH: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::InvalidStreamChunkLength as ErrorCode,
+        "不合法的数据块长度",
+    );
+
+    // 13306
+    pub const STREAM_RANGE_OUT_OF_BOUNDS: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::StreamRangeOutOfBounds as ErrorCode,
+        "请求的范围超出了数据流的长度",
+    );
+
     // 13401
     pub const FAILED_WHEN_CHECKING_CRC32_VALUE_OF_DECRYPTED_CONTENT: &'static CiftlError =
         &CiftlError::new(
@@ -203,4 +358,113 @@ pub mod predef {
         CrypterErrorCodeEnum::CannotEncryptEmptyString as ErrorCode,
         "不能对空串加密",
     );
+
+    // 13501
+    pub const FAILED_WHEN_UNPADDING: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenUnpadding as ErrorCode,
+        "去除填充时失败，填充内容不合法",
+    );
+
+    // 13502
+    pub const BLOCK_CRYPTER_REQUIRES_BLOCK_CIPHER_ALGORITHM: &'static CiftlError =
+        &CiftlError::new(
+            CrypterErrorCodeEnum::BlockCrypterRequiresBlockCipherAlgorithm as ErrorCode,
+            "分组加密器只能使用分组密码算法",
+        );
+
+    // 13601
+    pub const FAILED_WHEN_AEAD_ENCRYPTING: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenAeadEncrypting as ErrorCode,
+        "认证加密时失败",
+    );
+
+    // 13602
+    pub const FAILED_WHEN_AUTHENTICATING_CIPHERTEXT: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenAuthenticatingCiphertext as ErrorCode,
+        "密文认证失败，内容可能被篡改",
+    );
+
+    // 13701
+    pub const INVALID_KDF_PARAMETERS: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::InvalidKdfParameters as ErrorCode,
+        "不合法的密钥派生参数",
+    );
+
+    // 13702
+    pub const FAILED_WHEN_DERIVING_KEY: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::FailedWhenDerivingKey as ErrorCode,
+        "派生密钥时失败",
+    );
+
+    // 13801
+    pub const BAD_CIPHERTEXT_MAGIC: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::BadCiphertextMagic as ErrorCode,
+        "密文头部的魔数不匹配",
+    );
+
+    // 13802
+    pub const UNSUPPORTED_CIPHERTEXT_VERSION: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::UnsupportedCiphertextVersion as ErrorCode,
+        "不支持的密文版本",
+    );
+
+    // 13803
+    pub const UNKNOWN_ALGORITHM_IDENTIFIER: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::UnknownAlgorithmIdentifier as ErrorCode,
+        "未知的算法标识",
+    );
+
+    // 13804
+    pub const INCOMPATIBLE_ALGORITHM_COMBINATION: &'static CiftlError = &CiftlError::new(
+        CrypterErrorCodeEnum::IncompatibleAlgorithmCombination as ErrorCode,
+        "不兼容的算法组合",
+    );
+
+    // 14101
+    pub const HASHER_UNSATISFIED_KEY_LENGTH: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::HasherUnsatisfiedKeyLength as ErrorCode,
+        "不满足要求的哈希密钥长度",
+    );
+
+    // 14102
+    pub const INVALID_CRC_PARAMETERS: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::InvalidCrcParameters as ErrorCode,
+        "不合法的CRC参数",
+    );
+
+    // 14103
+    pub const UNKNOWN_HASH_ALGORITHM: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::UnknownHashAlgorithm as ErrorCode,
+        "未知的哈希算法",
+    );
+
+    // 14104
+    pub const HASH_ALGORITHM_NOT_ENABLED: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::HashAlgorithmNotEnabled as ErrorCode,
+        "当前构建中未启用的哈希算法",
+    );
+
+    // 14105
+    pub const FAILED_WHEN_READING_HASH_INPUT: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::FailedWhenReadingHashInput as ErrorCode,
+        "读取待哈希的数据失败",
+    );
+
+    // 14106
+    pub const INVALID_CHECKSUM_LINE: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::InvalidChecksumLine as ErrorCode,
+        "不合法的校验和行",
+    );
+
+    // 14107
+    pub const INVALID_MERKLE_CHUNK_LENGTH: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::InvalidMerkleChunkLength as ErrorCode,
+        "不合法的默克尔树数据块长度",
+    );
+
+    // 14108
+    pub const MERKLE_LEAF_INDEX_OUT_OF_RANGE: &'static CiftlError = &CiftlError::new(
+        HashErrorCodeEnum::MerkleLeafIndexOutOfRange as ErrorCode,
+        "默克尔树叶子节点的序号超出范围",
+    );
 }
@@ -0,0 +1,87 @@
+use ::adler::Adler32 as ExAdler32;
+
+use crate::hash::{HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// Adler-32的输出长度
+pub const ADLER32_OUTPUT_LENGTH: usize = 4;
+/// Adler-32默认的初始值
+pub const ADLER32_DEFAULT_SEED: u32 = 1;
+
+/// Adler-32校验操作，非密码学哈希，结果按大端序输出（与zlib中的存储方式一致）
+#[derive(Clone)]
+pub struct Adler32Hasher {
+    m_ctx: ExAdler32,
+    m_seed: u32,
+}
+
+impl Adler32Hasher {
+    /// 使用种子创建，种子即初始的校验值，可以用来接续之前计算的结果
+    pub fn with_seed(seed: u32) -> Self {
+        Adler32Hasher {
+            m_ctx: ExAdler32::from_checksum(seed),
+            m_seed: seed,
+        }
+    }
+
+    /// 获取校验的数值
+    pub fn value(&self) -> u32 {
+        self.m_ctx.checksum()
+    }
+}
+
+impl Default for Adler32Hasher {
+    fn default() -> Self {
+        Self::with_seed(ADLER32_DEFAULT_SEED)
+    }
+}
+
+impl HasherTrait for Adler32Hasher {
+    const OUTPUT_LENGTH: usize = ADLER32_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.write_slice(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.write_slice(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.value().to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Adler32Hasher {
+    fn reset(&mut self) {
+        self.m_ctx = ExAdler32::from_checksum(self.m_seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_adler32() {
+        let mut adler32_hasher = Adler32Hasher::default();
+        assert_eq!(adler32_hasher.value(), 1);
+        adler32_hasher.update_message("Wikipedia");
+        assert_eq!(
+            HexEncoding::default().encode(&adler32_hasher.finalize()),
+            "11E60398"
+        );
+        // 以之前的结果作为种子接续计算
+        let mut first = Adler32Hasher::default();
+        first.update_message("Wiki");
+        let mut second = Adler32Hasher::with_seed(first.value());
+        second.update_message("pedia");
+        assert_eq!(second.value(), 0x11E60398);
+    }
+}
@@ -0,0 +1,229 @@
+use ::blake2b_simd::{Params as ExBlake2bParams, State as ExBlake2bState};
+use ::blake2s_simd::{Params as ExBlake2sParams, State as ExBlake2sState};
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// BLAKE2b-512的输出长度
+pub const BLAKE2B512_OUTPUT_LENGTH: usize = 64;
+/// BLAKE2b的分组长度
+pub const BLAKE2B_BLOCK_LENGTH: usize = 128;
+/// BLAKE2b允许的最大密钥长度
+pub const BLAKE2B_MAX_KEY_LENGTH: usize = 64;
+/// BLAKE2s-256的输出长度
+pub const BLAKE2S256_OUTPUT_LENGTH: usize = 32;
+/// BLAKE2s的分组长度
+pub const BLAKE2S_BLOCK_LENGTH: usize = 64;
+/// BLAKE2s允许的最大密钥长度
+pub const BLAKE2S_MAX_KEY_LENGTH: usize = 32;
+
+/// BLAKE2b-512的哈希操作
+#[derive(Clone)]
+pub struct Blake2b512Hasher {
+    m_state: ExBlake2bState,
+    /// 刚创建时的状态，带密钥时已经包含了密钥分组，用于重置
+    m_initial_state: ExBlake2bState,
+}
+
+impl Blake2b512Hasher {
+    /// 使用密钥创建一个带密钥的BLAKE2b操作（即BLAKE2b的MAC模式），密钥长度为1到64字节
+    pub fn new_keyed(key: &[u8]) -> Result<Self> {
+        if key.is_empty() || key.len() > BLAKE2B_MAX_KEY_LENGTH {
+            return Err(HASHER_UNSATISFIED_KEY_LENGTH.add_opt_mess(&format!(
+                "BLAKE2b的密钥长度应为1到{}字节，实际为{}字节",
+                BLAKE2B_MAX_KEY_LENGTH,
+                key.len()
+            )));
+        }
+        Ok(Self::from_state(
+            ExBlake2bParams::new()
+                .hash_length(BLAKE2B512_OUTPUT_LENGTH)
+                .key(key)
+                .to_state(),
+        ))
+    }
+
+    fn from_state(state: ExBlake2bState) -> Self {
+        Blake2b512Hasher {
+            m_state: state.clone(),
+            m_initial_state: state,
+        }
+    }
+}
+
+impl Default for Blake2b512Hasher {
+    fn default() -> Self {
+        Self::from_state(
+            ExBlake2bParams::new()
+                .hash_length(BLAKE2B512_OUTPUT_LENGTH)
+                .to_state(),
+        )
+    }
+}
+
+impl HasherTrait for Blake2b512Hasher {
+    const OUTPUT_LENGTH: usize = BLAKE2B512_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_state.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_state.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_state.finalize().as_bytes().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_state.finalize().as_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Blake2b512Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_state = self.m_initial_state.clone();
+    }
+}
+
+impl BlockHasherTrait for Blake2b512Hasher {
+    const BLOCK_LENGTH: usize = BLAKE2B_BLOCK_LENGTH;
+}
+
+/// BLAKE2s-256的哈希操作
+#[derive(Clone)]
+pub struct Blake2s256Hasher {
+    m_state: ExBlake2sState,
+    /// 刚创建时的状态，带密钥时已经包含了密钥分组，用于重置
+    m_initial_state: ExBlake2sState,
+}
+
+impl Blake2s256Hasher {
+    /// 使用密钥创建一个带密钥的BLAKE2s操作（即BLAKE2s的MAC模式），密钥长度为1到32字节
+    pub fn new_keyed(key: &[u8]) -> Result<Self> {
+        if key.is_empty() || key.len() > BLAKE2S_MAX_KEY_LENGTH {
+            return Err(HASHER_UNSATISFIED_KEY_LENGTH.add_opt_mess(&format!(
+                "BLAKE2s的密钥长度应为1到{}字节，实际为{}字节",
+                BLAKE2S_MAX_KEY_LENGTH,
+                key.len()
+            )));
+        }
+        Ok(Self::from_state(
+            ExBlake2sParams::new()
+                .hash_length(BLAKE2S256_OUTPUT_LENGTH)
+                .key(key)
+                .to_state(),
+        ))
+    }
+
+    fn from_state(state: ExBlake2sState) -> Self {
+        Blake2s256Hasher {
+            m_state: state.clone(),
+            m_initial_state: state,
+        }
+    }
+}
+
+impl Default for Blake2s256Hasher {
+    fn default() -> Self {
+        Self::from_state(
+            ExBlake2sParams::new()
+                .hash_length(BLAKE2S256_OUTPUT_LENGTH)
+                .to_state(),
+        )
+    }
+}
+
+impl HasherTrait for Blake2s256Hasher {
+    const OUTPUT_LENGTH: usize = BLAKE2S256_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_state.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_state.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_state.finalize().as_bytes().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_state.finalize().as_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Blake2s256Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_state = self.m_initial_state.clone();
+    }
+}
+
+impl BlockHasherTrait for Blake2s256Hasher {
+    const BLOCK_LENGTH: usize = BLAKE2S_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    fn hash_hex<HR: HasherTrait>(mut hasher: HR, message: &[u8]) -> String {
+        hasher.update_bytes(message);
+        let res = hasher.finalize();
+        assert_eq!(res.len(), HR::OUTPUT_LENGTH);
+        HexEncoding::default().encode(&res)
+    }
+
+    #[test]
+    fn test_blake2b() {
+        // RFC 7693 附录A中的消息"abc"
+        assert_eq!(
+            hash_hex(Blake2b512Hasher::default(), b"abc"),
+            "BA80A53F981C4D0D6A2797B69F12F6E94C212F14685AC4B74B12BB6FDBFFA2D17D87C5392AAB792DC252D5DE4533CC9518D38AA8DBF1925AB92386EDD4009923"
+        );
+        assert_eq!(
+            hash_hex(Blake2b512Hasher::new_keyed(b"key").unwrap(), b"abc"),
+            "5C6A9A4AE911C02FB7E71A991EB9AEA371AE993D4842D206E6020D46F5E41358C6D5C277C110EF86C959ED63E6ECAAACEAAFF38019A43264AE06ACF73B9550B1"
+        );
+        // 官方带密钥测试向量的第一条
+        let key: ByteVector = (0x00..0x40).collect();
+        assert_eq!(
+            hash_hex(Blake2b512Hasher::new_keyed(&key).unwrap(), b""),
+            "10EBB67700B1868EFB4417987ACF4690AE9D972FB7A590C2F02871799AAA4786B5E996E8F0F4EB981FC214B005F42D2FF4233499391653DF7AEFCBC13FC51568"
+        );
+        // 非法的密钥长度
+        let res = Blake2b512Hasher::new_keyed(&[0x00; 65]);
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+        assert!(Blake2b512Hasher::new_keyed(b"").is_err());
+    }
+
+    #[test]
+    fn test_blake2s() {
+        // RFC 7693 附录B中的消息"abc"
+        assert_eq!(
+            hash_hex(Blake2s256Hasher::default(), b"abc"),
+            "508C5E8C327C14E2E1A72BA34EEB452F37458B209ED63A294D999B4C86675982"
+        );
+        assert_eq!(
+            hash_hex(Blake2s256Hasher::new_keyed(b"key").unwrap(), b"abc"),
+            "3F9723437B033BF0C1F4DF43CAFD0776068CB0A95912DE13F3B2952A3ABA764D"
+        );
+        let key: ByteVector = (0x00..0x20).collect();
+        assert_eq!(
+            hash_hex(Blake2s256Hasher::new_keyed(&key).unwrap(), b""),
+            "48A8997DA407876B3D79C0D92325AD3B89CBB754D86AB71AEE047AD345FD2C49"
+        );
+        let res = Blake2s256Hasher::new_keyed(&[0x00; 33]);
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+    }
+}
@@ -0,0 +1,125 @@
+use ::blake3::Hasher as ExBlake3Hasher;
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// BLAKE3默认的输出长度
+pub const BLAKE3_OUTPUT_LENGTH: usize = 32;
+/// BLAKE3的分组长度
+pub const BLAKE3_BLOCK_LENGTH: usize = 64;
+/// BLAKE3带密钥模式的密钥长度
+pub const BLAKE3_KEY_LENGTH: usize = 32;
+
+/// BLAKE3的哈希操作，支持普通、带密钥和密钥派生三种模式
+#[derive(Clone, Default)]
+pub struct Blake3Hasher {
+    m_ctx: ExBlake3Hasher,
+}
+
+impl Blake3Hasher {
+    /// 使用32字节的密钥创建一个带密钥的BLAKE3操作
+    pub fn new_keyed(key: &[u8]) -> Result<Self> {
+        let key: &[u8; BLAKE3_KEY_LENGTH] = key.try_into().map_err(|_| {
+            HASHER_UNSATISFIED_KEY_LENGTH.add_opt_mess(&format!(
+                "BLAKE3的密钥长度应为{}字节，实际为{}字节",
+                BLAKE3_KEY_LENGTH,
+                key.len()
+            ))
+        })?;
+        Ok(Blake3Hasher {
+            m_ctx: ExBlake3Hasher::new_keyed(key),
+        })
+    }
+
+    /// 使用上下文字符串创建一个密钥派生模式的BLAKE3操作，输入的数据即密钥材料
+    /// 上下文字符串应当是硬编码、全局唯一且与具体应用相关的
+    pub fn new_derive_key(context: &str) -> Self {
+        Blake3Hasher {
+            m_ctx: ExBlake3Hasher::new_derive_key(context),
+        }
+    }
+
+    /// 获取任意长度的结果
+    pub fn finalize_xof(&self, n: usize) -> ByteVector {
+        let mut res = vec![0x00; n];
+        self.m_ctx.finalize_xof().fill(&mut res);
+        res
+    }
+}
+
+impl HasherTrait for Blake3Hasher {
+    const OUTPUT_LENGTH: usize = BLAKE3_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.finalize().as_bytes().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_ctx.finalize().as_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Blake3Hasher {
+    /// 恢复到刚创建时的状态，带密钥和密钥派生模式的参数保持不变
+    fn reset(&mut self) {
+        self.m_ctx.reset();
+    }
+}
+
+impl BlockHasherTrait for Blake3Hasher {
+    const BLOCK_LENGTH: usize = BLAKE3_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_blake3() {
+        let mut hasher = Blake3Hasher::default();
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize()),
+            "AF1349B9F5F9A1A6A0404DEA36DCC9499BCB25C9ADC112B7CC9A93CAE41F3262"
+        );
+        hasher.update_message("abc");
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize()),
+            "6437B3AC38465133FFB63B75273A8DB548C558465D79DB03FD359C6CD5BD9D85"
+        );
+        // 可变长度输出的前缀与默认输出相同
+        let xof = hasher.finalize_xof(100);
+        assert_eq!(xof.len(), 100);
+        assert_eq!(xof[..BLAKE3_OUTPUT_LENGTH], hasher.finalize()[..]);
+    }
+
+    #[test]
+    fn test_blake3_keyed_and_derive_key() {
+        // 官方测试向量中的空输入
+        let hasher = Blake3Hasher::new_keyed(b"whats the Elvish word for friend").unwrap();
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize()),
+            "92B2B75604ED3C761F9D6F62392C8A9227AD0EA3F09573E783F1498A4ED60D26"
+        );
+        let hasher =
+            Blake3Hasher::new_derive_key("BLAKE3 2019-12-27 16:29:52 test vectors context");
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize()),
+            "2CC39783C223154FEA8DFB7C1B1660F2AC2DCBD1C1DE8277B0B0DD39B7E50D7D"
+        );
+        // 非法的密钥长度
+        let res = Blake3Hasher::new_keyed(b"short key");
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+    }
+}
@@ -0,0 +1,425 @@
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::encoding::hex::{HexEncoding, HexEncodingCase};
+use crate::encoding::EncodingTrait;
+use crate::hash::HashAlgorithm;
+use crate::*;
+
+/// 校验和清单的格式
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChecksumFormat {
+    /// GNU coreutils的格式，如sha256sum输出的"<摘要>  <文件名>"
+    Gnu,
+    /// BSD的带标签格式，如"SHA256 (<文件名>) = <摘要>"
+    Bsd,
+}
+
+/// 校验和清单中的一行
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChecksumEntry {
+    /// 哈希算法，GNU格式的行中不包含算法，由调用者指定
+    pub algorithm: HashAlgorithm,
+    /// 摘要
+    pub digest: ByteVector,
+    /// 文件名
+    pub path: String,
+    /// 是否为二进制模式（GNU格式中文件名前的'*'），不影响摘要的计算
+    pub binary: bool,
+}
+
+/// 校验一个文件的结果
+#[derive(Clone, Debug)]
+pub enum ChecksumStatus {
+    /// 摘要一致
+    Ok,
+    /// 摘要不一致
+    Failed,
+    /// 文件不存在
+    Missing,
+    /// 文件存在但无法读取
+    Unreadable(CiftlError),
+}
+
+/// BSD格式中算法的标签，与coreutils一致，其余算法使用名称的大写形式
+fn checksum_tag(algorithm: HashAlgorithm) -> String {
+    match algorithm {
+        HashAlgorithm::Blake2b512 => "BLAKE2b".to_string(),
+        _ => algorithm.name().to_uppercase(),
+    }
+}
+
+/// 从BSD格式的标签解析算法
+fn parse_checksum_tag(tag: &str) -> Result<HashAlgorithm> {
+    if tag.eq_ignore_ascii_case("BLAKE2b") {
+        return Ok(HashAlgorithm::Blake2b512);
+    }
+    tag.parse::<HashAlgorithm>()
+}
+
+/// 文件名中含有'\\'、换行或回车时需要转义，此时整行以'\\'开头
+fn needs_escape(path: &str) -> bool {
+    path.contains(['\\', '\n', '\r'])
+}
+
+fn escape_path(path: &str) -> String {
+    path.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape_path(path: &str) -> Result<String> {
+    let mut res = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => res.push('\\'),
+            Some('n') => res.push('\n'),
+            Some('r') => res.push('\r'),
+            _ => return Err(INVALID_CHECKSUM_LINE.add_opt_mess("文件名中含有不合法的转义")),
+        }
+    }
+    Ok(res)
+}
+
+/// 解码摘要并检查其长度是否与算法的输出长度一致
+fn decode_digest(algorithm: HashAlgorithm, digest: &str) -> Result<ByteVector> {
+    let res = HexEncoding::default()
+        .decode(digest)
+        .map_err(|e| INVALID_CHECKSUM_LINE.add_opt_mess(&format!("{e}")))?;
+    if res.len() != algorithm.output_length() {
+        return Err(INVALID_CHECKSUM_LINE.add_opt_mess(&format!(
+            "{}的摘要长度应为{}字节，实际为{}字节",
+            algorithm,
+            algorithm.output_length(),
+            res.len()
+        )));
+    }
+    Ok(res)
+}
+
+impl ChecksumEntry {
+    /// 计算一个文件的摘要
+    pub fn from_file<P: AsRef<Path>>(algorithm: HashAlgorithm, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        Ok(ChecksumEntry {
+            algorithm,
+            digest: algorithm.hash_file(path)?,
+            path: path.to_string_lossy().into_owned(),
+            binary: false,
+        })
+    }
+
+    /// 解析清单中的一行，自动识别GNU格式和BSD格式
+    /// GNU格式的行中不包含算法，使用default_algorithm
+    pub fn parse(line: &str, default_algorithm: HashAlgorithm) -> Result<Self> {
+        let (escaped, line) = match line.strip_prefix('\\') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let unescape = |path: &str| -> Result<String> {
+            if escaped {
+                unescape_path(path)
+            } else {
+                Ok(path.to_string())
+            }
+        };
+        // BSD格式：<标签> (<文件名>) = <摘要>
+        if let (Some(open), Some(close)) = (line.find(" ("), line.rfind(") = ")) {
+            if open < close && !line[..open].contains(' ') {
+                let algorithm = parse_checksum_tag(&line[..open])
+                    .map_err(|e| INVALID_CHECKSUM_LINE.add_opt_mess(&format!("{e}")))?;
+                return Ok(ChecksumEntry {
+                    algorithm,
+                    digest: decode_digest(algorithm, &line[close + 4..])?,
+                    path: unescape(&line[open + 2..close])?,
+                    binary: false,
+                });
+            }
+        }
+        // GNU格式：<摘要>  <文件名>或<摘要> *<文件名>
+        let (digest, rest) = line
+            .split_once(' ')
+            .ok_or_else(|| INVALID_CHECKSUM_LINE.add_opt_mess(line))?;
+        let (binary, path) = match rest.chars().next() {
+            Some(' ') => (false, &rest[1..]),
+            Some('*') => (true, &rest[1..]),
+            _ => return Err(INVALID_CHECKSUM_LINE.add_opt_mess(line)),
+        };
+        if path.is_empty() {
+            return Err(INVALID_CHECKSUM_LINE.add_opt_mess("缺少文件名"));
+        }
+        Ok(ChecksumEntry {
+            algorithm: default_algorithm,
+            digest: decode_digest(default_algorithm, digest)?,
+            path: unescape(path)?,
+            binary,
+        })
+    }
+
+    /// 按照指定的格式输出一行，不包含换行符
+    pub fn to_line(&self, format: ChecksumFormat) -> String {
+        let digest = HexEncoding::new(HexEncodingCase::LowerCase).encode(&self.digest);
+        let (prefix, path) = if needs_escape(&self.path) {
+            ("\\", escape_path(&self.path))
+        } else {
+            ("", self.path.clone())
+        };
+        match format {
+            ChecksumFormat::Gnu => {
+                let mode = if self.binary { '*' } else { ' ' };
+                format!("{prefix}{digest} {mode}{path}")
+            }
+            ChecksumFormat::Bsd => {
+                format!(
+                    "{prefix}{} ({path}) = {digest}",
+                    checksum_tag(self.algorithm)
+                )
+            }
+        }
+    }
+
+    /// 重新计算文件的摘要并与记录的摘要比较
+    pub fn check(&self) -> ChecksumStatus {
+        match std::fs::metadata(&self.path) {
+            Err(e) if e.kind() == ErrorKind::NotFound => return ChecksumStatus::Missing,
+            _ => {}
+        }
+        match self.algorithm.hash_file(&self.path) {
+            Ok(digest) if digest == self.digest => ChecksumStatus::Ok,
+            Ok(_) => ChecksumStatus::Failed,
+            Err(e) => ChecksumStatus::Unreadable(e),
+        }
+    }
+}
+
+/// 校验清单时的选项
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct ChecksumCheckOptions {
+    /// 忽略不存在的文件，与coreutils的--ignore-missing相同，这些文件不会出现在结果中
+    pub ignore_missing: bool,
+}
+
+/// 校验整个清单的结果
+#[derive(Clone, Debug, Default)]
+pub struct ChecksumReport {
+    /// 每个文件的校验结果
+    pub results: Vec<(ChecksumEntry, ChecksumStatus)>,
+    /// 格式不正确的行，行号从1开始
+    pub invalid_lines: Vec<(usize, CiftlError)>,
+}
+
+impl ChecksumReport {
+    /// 摘要不一致的文件数
+    pub fn failed_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|(_, status)| matches!(status, ChecksumStatus::Failed))
+            .count()
+    }
+
+    /// 实际完成校验的文件数，即摘要一致或不一致的文件数
+    pub fn verified_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|(_, status)| matches!(status, ChecksumStatus::Ok | ChecksumStatus::Failed))
+            .count()
+    }
+
+    /// 不存在或无法读取的文件数
+    pub fn missing_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|(_, status)| {
+                matches!(
+                    status,
+                    ChecksumStatus::Missing | ChecksumStatus::Unreadable(_)
+                )
+            })
+            .count()
+    }
+
+    /// 所有文件都通过校验，且没有格式不正确的行
+    pub fn is_ok(&self) -> bool {
+        !self.results.is_empty()
+            && self.invalid_lines.is_empty()
+            && self
+                .results
+                .iter()
+                .all(|(_, status)| matches!(status, ChecksumStatus::Ok))
+    }
+}
+
+/// 计算多个文件的摘要并生成清单，每行以换行符结尾
+pub fn generate_manifest<P: AsRef<Path>>(
+    algorithm: HashAlgorithm,
+    paths: &[P],
+    format: ChecksumFormat,
+) -> Result<String> {
+    let mut res = String::new();
+    for path in paths {
+        res.push_str(&ChecksumEntry::from_file(algorithm, path)?.to_line(format));
+        res.push('\n');
+    }
+    Ok(res)
+}
+
+/// 逐行解析清单，忽略空行
+pub fn parse_manifest(
+    content: &str,
+    default_algorithm: HashAlgorithm,
+) -> Vec<(usize, Result<ChecksumEntry>)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, ChecksumEntry::parse(line, default_algorithm)))
+        .collect()
+}
+
+/// 校验清单中的所有文件
+pub fn check_manifest(content: &str, default_algorithm: HashAlgorithm) -> ChecksumReport {
+    check_manifest_with_options(content, default_algorithm, ChecksumCheckOptions::default())
+}
+
+/// 按照指定的选项校验清单中的所有文件
+pub fn check_manifest_with_options(
+    content: &str,
+    default_algorithm: HashAlgorithm,
+    options: ChecksumCheckOptions,
+) -> ChecksumReport {
+    let mut report = ChecksumReport::default();
+    for (line_number, entry) in parse_manifest(content, default_algorithm) {
+        match entry {
+            Ok(entry) => {
+                let status = entry.check();
+                if options.ignore_missing && matches!(status, ChecksumStatus::Missing) {
+                    continue;
+                }
+                report.results.push((entry, status));
+            }
+            Err(e) => report.invalid_lines.push((line_number, e)),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在临时目录中创建文件并返回其路径
+    fn temp_file(name: &str, content: &[u8]) -> String {
+        let path =
+            std::env::temp_dir().join(format!("ciftl_checksum_test_{}_{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_checksum_line() {
+        // 与sha256sum和sha256sum --tag的输出一致
+        let line = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  abc.txt";
+        let entry = ChecksumEntry::parse(line, HashAlgorithm::Sha256).unwrap();
+        assert_eq!(entry.path, "abc.txt");
+        assert!(!entry.binary);
+        assert_eq!(entry.to_line(ChecksumFormat::Gnu), line);
+        assert_eq!(
+            entry.to_line(ChecksumFormat::Bsd),
+            "SHA256 (abc.txt) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // BSD格式的行自带算法
+        let bsd_line = "SM3 (a (1)) = b.txt) = 66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0";
+        let entry = ChecksumEntry::parse(bsd_line, HashAlgorithm::Sha256).unwrap();
+        assert_eq!(entry.algorithm, HashAlgorithm::Sm3);
+        assert_eq!(entry.path, "a (1)) = b.txt");
+        assert_eq!(entry.to_line(ChecksumFormat::Bsd), bsd_line);
+        // 二进制模式
+        let line = "a9993e364706816aba3e25717850c26c9cd0d89d *abc.bin";
+        let entry = ChecksumEntry::parse(line, HashAlgorithm::Sha1).unwrap();
+        assert!(entry.binary);
+        assert_eq!(entry.path, "abc.bin");
+        assert_eq!(entry.to_line(ChecksumFormat::Gnu), line);
+        // 文件名中的特殊字符需要转义
+        let entry = ChecksumEntry {
+            path: "a\\b\nc".to_string(),
+            ..entry
+        };
+        let line = entry.to_line(ChecksumFormat::Gnu);
+        assert_eq!(
+            line,
+            "\\a9993e364706816aba3e25717850c26c9cd0d89d *a\\\\b\\nc"
+        );
+        assert_eq!(
+            ChecksumEntry::parse(&line, HashAlgorithm::Sha1).unwrap(),
+            entry
+        );
+        // 格式不正确的行
+        for line in [
+            "abc.txt",
+            "a9993e364706816aba3e25717850c26c9cd0d89d abc.txt",
+            "a9993e364706816aba3e25717850c26c9cd0d89d  ",
+            "a9993e364706816aba3e25717850c26c9cd0d8  abc.txt",
+            "a9993e364706816aba3e25717850c26c9cd0d89d  abc.txt",
+            "XYZ (abc.txt) = a9993e364706816aba3e25717850c26c9cd0d89d",
+        ] {
+            let res = ChecksumEntry::parse(line, HashAlgorithm::Sha256);
+            assert!(res.is_err());
+            println!("错误：{}", res.err().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_check_manifest() {
+        let good = temp_file("good.txt", b"abc");
+        let bad = temp_file("bad.txt", b"abd");
+        let missing = format!("{good}.missing");
+        for format in [ChecksumFormat::Gnu, ChecksumFormat::Bsd] {
+            let manifest =
+                generate_manifest(HashAlgorithm::Sha256, &[&good, &bad], format).unwrap();
+            let report = check_manifest(&manifest, HashAlgorithm::Sha256);
+            assert!(report.is_ok());
+            assert_eq!(report.results.len(), 2);
+        }
+        let manifest = generate_manifest(HashAlgorithm::Sha256, &[&good], ChecksumFormat::Gnu)
+            .unwrap()
+            + &format!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  {bad}\n\n"
+            )
+            + &format!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  {missing}\n"
+            )
+            + "not a checksum line\n";
+        let report = check_manifest(&manifest, HashAlgorithm::Sha256);
+        assert!(!report.is_ok());
+        assert!(matches!(report.results[0].1, ChecksumStatus::Ok));
+        assert!(matches!(report.results[1].1, ChecksumStatus::Failed));
+        assert!(matches!(report.results[2].1, ChecksumStatus::Missing));
+        assert_eq!(report.failed_count(), 1);
+        assert_eq!(report.missing_count(), 1);
+        assert_eq!(report.invalid_lines.len(), 1);
+        assert_eq!(report.invalid_lines[0].0, 5);
+        assert_eq!(report.verified_count(), 2);
+        // 忽略不存在的文件
+        let options = ChecksumCheckOptions {
+            ignore_missing: true,
+        };
+        let report = check_manifest_with_options(&manifest, HashAlgorithm::Sha256, options);
+        assert_eq!(report.results.len(), 2);
+        assert_eq!(report.missing_count(), 0);
+        assert_eq!(report.verified_count(), 2);
+        // 所有文件都不存在时没有文件完成校验
+        let manifest = format!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  {missing}\n"
+        );
+        let report = check_manifest_with_options(&manifest, HashAlgorithm::Sha256, options);
+        assert_eq!(report.verified_count(), 0);
+        assert!(!report.is_ok());
+        std::fs::remove_file(&good).unwrap();
+        std::fs::remove_file(&bad).unwrap();
+    }
+}
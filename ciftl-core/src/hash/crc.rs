@@ -1,9 +1,11 @@
 use std::hash::Hasher as _;
+use std::marker;
+
 // crc32fast和crc32c是两个库，要注意区分
 use crc32c::Crc32cHasher as ExCrc32cHasher;
 use crc32fast::Hasher as Crc32FastHasher;
 
-use crate::hash::HasherTrait;
+use crate::hash::{HasherTrait, ResettableHasherTrait};
 use crate::*;
 
 pub const CRC32_OUTPUT_LENGTH: usize = 4;
@@ -38,6 +40,16 @@ impl HasherTrait for Crc32Hasher {
         let checksum_bytes = checksum.to_le_bytes();
         checksum_bytes.to_vec()
     }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_hasher.clone().finalize().to_le_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Crc32Hasher {
+    fn reset(&mut self) {
+        self.m_hasher.reset();
+    }
 }
 
 /// CRC32c 哈希实现
@@ -70,8 +82,354 @@ impl HasherTrait for Crc32cHasher {
         let checksum_bytes = checksum.to_le_bytes();
         checksum_bytes.to_vec()
     }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&(self.m_hasher.finish() as u32).to_le_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Crc32cHasher {
+    fn reset(&mut self) {
+        self.m_hasher = ExCrc32cHasher::default();
+    }
+}
+
+/// CRC算法的参数模型，各字段的含义与CRC RevEng目录中的相同
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CrcParameters {
+    /// 位宽，取值为1到64
+    pub width: u8,
+    /// 生成多项式（不含最高次项）
+    pub poly: u64,
+    /// 寄存器的初始值
+    pub init: u64,
+    /// 输入的每个字节是否按位反转
+    pub refin: bool,
+    /// 输出前结果是否整体按位反转
+    pub refout: bool,
+    /// 与结果异或的值
+    pub xorout: u64,
+}
+
+impl CrcParameters {
+    /// 位宽对应的掩码
+    const fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+
+    /// 参数是否合法，要求位宽在1到64之间且多项式、初始值和异或值不超出位宽
+    pub const fn is_valid(&self) -> bool {
+        if self.width == 0 || self.width > 64 {
+            return false;
+        }
+        let mask = self.mask();
+        self.poly & !mask == 0 && self.init & !mask == 0 && self.xorout & !mask == 0
+    }
+
+    /// 结果的字节数
+    pub const fn output_length(&self) -> usize {
+        (self.width as usize).div_ceil(8)
+    }
+}
+
+/// CRC-16/CCITT（即CRC-16/KERMIT），输入输出均反转
+pub const CRC16_CCITT_PARAMETERS: CrcParameters = CrcParameters {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+};
+/// CRC-16/CCITT-FALSE（即CRC-16/IBM-3740），常被误称为CRC-16/CCITT，注意与上面的区分
+pub const CRC16_CCITT_FALSE_PARAMETERS: CrcParameters = CrcParameters {
+    width: 16,
+    poly: 0x1021,
+    init: 0xFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+};
+/// CRC-16/MODBUS
+pub const CRC16_MODBUS_PARAMETERS: CrcParameters = CrcParameters {
+    width: 16,
+    poly: 0x8005,
+    init: 0xFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+};
+/// CRC-32/BZIP2
+pub const CRC32_BZIP2_PARAMETERS: CrcParameters = CrcParameters {
+    width: 32,
+    poly: 0x04C11DB7,
+    init: 0xFFFFFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0xFFFFFFFF,
+};
+/// CRC-64/ECMA-182
+pub const CRC64_ECMA_PARAMETERS: CrcParameters = CrcParameters {
+    width: 64,
+    poly: 0x42F0E1EBA9EA3693,
+    init: 0x0000000000000000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000000000000000,
+};
+/// CRC-64/XZ，与CRC-64/ECMA-182使用相同的多项式
+pub const CRC64_XZ_PARAMETERS: CrcParameters = CrcParameters {
+    width: 64,
+    poly: 0x42F0E1EBA9EA3693,
+    init: 0xFFFFFFFFFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFFFFFFFFFF,
+};
+
+/// 将value的低width位按位反转
+fn reflect(value: u64, width: u8) -> u64 {
+    value.reverse_bits() >> (64 - width)
+}
+
+/// 参数化的CRC计算引擎，参数在运行时指定
+/// 本模块中所有CRC哈希操作的结果都按小端序输出，大端序的结果可以通过to_be_bytes获取
+/// 输入反转时寄存器中保存反转后的值，否则寄存器的值左对齐到最高位，从而统一按字节查表
+#[derive(Clone)]
+pub struct CrcEngine {
+    m_params: CrcParameters,
+    m_table: [u64; 256],
+    m_register: u64,
+}
+
+impl CrcEngine {
+    /// 使用给定的参数创建一个CRC计算引擎
+    pub fn new(params: CrcParameters) -> Result<Self> {
+        if !params.is_valid() {
+            return Err(INVALID_CRC_PARAMETERS.add_opt_mess(&format!("{params:?}")));
+        }
+        let mut table = [0u64; 256];
+        if params.refin {
+            let poly = reflect(params.poly, params.width);
+            for (i, entry) in table.iter_mut().enumerate() {
+                let mut c = i as u64;
+                for _ in 0..8 {
+                    c = if c & 1 != 0 { (c >> 1) ^ poly } else { c >> 1 };
+                }
+                *entry = c;
+            }
+        } else {
+            let poly = params.poly << (64 - params.width);
+            for (i, entry) in table.iter_mut().enumerate() {
+                let mut c = (i as u64) << 56;
+                for _ in 0..8 {
+                    c = if c >> 63 != 0 {
+                        (c << 1) ^ poly
+                    } else {
+                        c << 1
+                    };
+                }
+                *entry = c;
+            }
+        }
+        let mut engine = CrcEngine {
+            m_params: params,
+            m_table: table,
+            m_register: 0,
+        };
+        engine.m_register = engine.initial_register();
+        Ok(engine)
+    }
+
+    /// 寄存器的初始状态
+    fn initial_register(&self) -> u64 {
+        if self.m_params.refin {
+            reflect(self.m_params.init, self.m_params.width)
+        } else {
+            self.m_params.init << (64 - self.m_params.width)
+        }
+    }
+
+    /// 获取参数
+    pub fn parameters(&self) -> &CrcParameters {
+        &self.m_params
+    }
+
+    /// 输入数据
+    pub fn update(&mut self, data: &[u8]) {
+        if self.m_params.refin {
+            for b in data {
+                self.m_register = self.m_table[((self.m_register ^ *b as u64) & 0xFF) as usize]
+                    ^ (self.m_register >> 8);
+            }
+        } else {
+            for b in data {
+                self.m_register = self.m_table[((self.m_register >> 56) ^ *b as u64) as usize]
+                    ^ (self.m_register << 8);
+            }
+        }
+    }
+
+    /// 获取CRC的数值
+    pub fn value(&self) -> u64 {
+        let params = &self.m_params;
+        let value = if params.refin {
+            self.m_register
+        } else {
+            self.m_register >> (64 - params.width)
+        };
+        // 寄存器的方向与输入一致，输出的方向不同时需要再反转一次
+        let value = if params.refin != params.refout {
+            reflect(value, params.width)
+        } else {
+            value
+        };
+        value ^ params.xorout
+    }
+
+    /// 获取CRC的小端序字节表示
+    pub fn to_le_bytes(&self) -> ByteVector {
+        self.value().to_le_bytes()[..self.m_params.output_length()].to_vec()
+    }
+
+    /// 获取CRC的大端序字节表示，与通常书写CRC值的方式一致
+    pub fn to_be_bytes(&self) -> ByteVector {
+        self.value().to_be_bytes()[8 - self.m_params.output_length()..].to_vec()
+    }
+
+    /// 获取CRC的字节表示，与Crc32Hasher和Crc32cHasher一致，按小端序输出
+    /// 需要按书写顺序输出时使用to_be_bytes
+    pub fn finalize(&self) -> ByteVector {
+        self.to_le_bytes()
+    }
+
+    /// 将CRC的小端序字节表示写入out，out的长度必须与参数的输出长度一致
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_le_bytes()[..self.m_params.output_length()]);
+    }
+
+    /// 恢复到刚创建时的状态，不需要重新生成查找表
+    pub fn reset(&mut self) {
+        self.m_register = self.initial_register();
+    }
 }
 
+/// CRC预设trait，为一个类型指定CRC参数即可得到对应的CrcHasher
+pub trait CrcPresetTrait {
+    const PARAMETERS: CrcParameters;
+}
+
+/// 由预设参数确定的CRC哈希操作
+/// 与本模块中的其它CRC哈希操作相同，结果按小端序输出
+pub struct CrcHasher<P: CrcPresetTrait> {
+    m_engine: CrcEngine,
+    _p: marker::PhantomData<P>,
+}
+
+impl<P: CrcPresetTrait> CrcHasher<P> {
+    /// 在编译期检查预设参数是否合法
+    const PARAMETERS_CHECK: () = assert!(P::PARAMETERS.is_valid(), "不合法的CRC参数");
+
+    /// 获取CRC的数值
+    pub fn value(&self) -> u64 {
+        self.m_engine.value()
+    }
+
+    /// 获取CRC的大端序字节表示，与通常书写CRC值的方式一致
+    pub fn to_be_bytes(&self) -> ByteVector {
+        self.m_engine.to_be_bytes()
+    }
+}
+
+impl<P: CrcPresetTrait> Default for CrcHasher<P> {
+    fn default() -> Self {
+        let () = Self::PARAMETERS_CHECK;
+        CrcHasher::<P> {
+            m_engine: CrcEngine::new(P::PARAMETERS).unwrap(),
+            _p: marker::PhantomData::<P>,
+        }
+    }
+}
+
+impl<P: CrcPresetTrait> Clone for CrcHasher<P> {
+    fn clone(&self) -> Self {
+        CrcHasher::<P> {
+            m_engine: self.m_engine.clone(),
+            _p: marker::PhantomData::<P>,
+        }
+    }
+}
+
+impl<P: CrcPresetTrait> HasherTrait for CrcHasher<P> {
+    const OUTPUT_LENGTH: usize = P::PARAMETERS.output_length();
+
+    fn update_message(&mut self, message: &str) {
+        self.m_engine.update(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_engine.update(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.m_engine.finalize()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        self.m_engine.finalize_into(out);
+    }
+}
+
+impl<P: CrcPresetTrait> ResettableHasherTrait for CrcHasher<P> {
+    fn reset(&mut self) {
+        self.m_engine.reset();
+    }
+}
+
+/// CRC-16/CCITT的预设
+pub struct Crc16CcittPreset;
+impl CrcPresetTrait for Crc16CcittPreset {
+    const PARAMETERS: CrcParameters = CRC16_CCITT_PARAMETERS;
+}
+/// CRC-16/CCITT-FALSE的预设
+pub struct Crc16CcittFalsePreset;
+impl CrcPresetTrait for Crc16CcittFalsePreset {
+    const PARAMETERS: CrcParameters = CRC16_CCITT_FALSE_PARAMETERS;
+}
+/// CRC-16/MODBUS的预设
+pub struct Crc16ModbusPreset;
+impl CrcPresetTrait for Crc16ModbusPreset {
+    const PARAMETERS: CrcParameters = CRC16_MODBUS_PARAMETERS;
+}
+/// CRC-32/BZIP2的预设
+pub struct Crc32Bzip2Preset;
+impl CrcPresetTrait for Crc32Bzip2Preset {
+    const PARAMETERS: CrcParameters = CRC32_BZIP2_PARAMETERS;
+}
+/// CRC-64/ECMA-182的预设
+pub struct Crc64EcmaPreset;
+impl CrcPresetTrait for Crc64EcmaPreset {
+    const PARAMETERS: CrcParameters = CRC64_ECMA_PARAMETERS;
+}
+/// CRC-64/XZ的预设
+pub struct Crc64XzPreset;
+impl CrcPresetTrait for Crc64XzPreset {
+    const PARAMETERS: CrcParameters = CRC64_XZ_PARAMETERS;
+}
+
+/// CRC-16/CCITT
+pub type Crc16CcittHasher = CrcHasher<Crc16CcittPreset>;
+/// CRC-16/CCITT-FALSE
+pub type Crc16CcittFalseHasher = CrcHasher<Crc16CcittFalsePreset>;
+/// CRC-16/MODBUS
+pub type Crc16ModbusHasher = CrcHasher<Crc16ModbusPreset>;
+/// CRC-32/BZIP2
+pub type Crc32Bzip2Hasher = CrcHasher<Crc32Bzip2Preset>;
+/// CRC-64/ECMA-182
+pub type Crc64EcmaHasher = CrcHasher<Crc64EcmaPreset>;
+/// CRC-64/XZ
+pub type Crc64XzHasher = CrcHasher<Crc64XzPreset>;
+
 #[cfg(test)]
 mod tests {
 
@@ -110,4 +468,94 @@ mod tests {
         let res = hexe.encode(&res);
         assert_eq!("1189D92E".to_string(), res);
     }
+
+    /// 各预设对"123456789"的校验值，取自CRC RevEng目录
+    #[test]
+    fn test_crc_presets() {
+        fn check<P: CrcPresetTrait>(expected: u64) {
+            let mut hasher = CrcHasher::<P>::default();
+            hasher.update_message("123456789");
+            assert_eq!(hasher.value(), expected);
+            let res = hasher.finalize();
+            assert_eq!(res.len(), CrcHasher::<P>::OUTPUT_LENGTH);
+            assert_eq!(res, expected.to_le_bytes()[..res.len()].to_vec());
+            assert_eq!(
+                hasher.to_be_bytes(),
+                expected.to_be_bytes()[8 - res.len()..].to_vec()
+            );
+        }
+        check::<Crc16CcittPreset>(0x2189);
+        check::<Crc16CcittFalsePreset>(0x29B1);
+        check::<Crc16ModbusPreset>(0x4B37);
+        check::<Crc32Bzip2Preset>(0xFC891918);
+        check::<Crc64EcmaPreset>(0x6C40DF5F0B497347);
+        check::<Crc64XzPreset>(0x995DC9BBDF1939FA);
+    }
+
+    #[test]
+    fn test_crc_engine() {
+        // CRC-32/ISO-HDLC与Crc32Hasher的结果相同
+        let mut engine = CrcEngine::new(CrcParameters {
+            width: 32,
+            poly: 0x04C11DB7,
+            init: 0xFFFFFFFF,
+            refin: true,
+            refout: true,
+            xorout: 0xFFFFFFFF,
+        })
+        .unwrap();
+        let mut crc32_hasher = Crc32Hasher::default();
+        for chunk in ["123", "456", "78910"] {
+            engine.update(chunk.as_bytes());
+            crc32_hasher.update_message(chunk);
+        }
+        assert_eq!(engine.finalize(), crc32_hasher.finalize());
+        let mut expected = crc32_hasher.finalize();
+        expected.reverse();
+        assert_eq!(engine.to_be_bytes(), expected);
+        // 位宽不是8的整数倍的情况：CRC-5/USB和CRC-7/MMC
+        let mut engine = CrcEngine::new(CrcParameters {
+            width: 5,
+            poly: 0x05,
+            init: 0x1F,
+            refin: true,
+            refout: true,
+            xorout: 0x1F,
+        })
+        .unwrap();
+        engine.update(b"123456789");
+        assert_eq!(engine.value(), 0x19);
+        let mut engine = CrcEngine::new(CrcParameters {
+            width: 7,
+            poly: 0x09,
+            init: 0x00,
+            refin: false,
+            refout: false,
+            xorout: 0x00,
+        })
+        .unwrap();
+        engine.update(b"123456789");
+        assert_eq!(engine.value(), 0x75);
+        assert_eq!(engine.finalize().len(), 1);
+        // 输入不反转而输出反转的情况：CRC-12/UMTS
+        let mut engine = CrcEngine::new(CrcParameters {
+            width: 12,
+            poly: 0x80F,
+            init: 0x000,
+            refin: false,
+            refout: true,
+            xorout: 0x000,
+        })
+        .unwrap();
+        engine.update(b"123456789");
+        assert_eq!(engine.value(), 0xDAF);
+        // 不合法的参数
+        let mut params = CRC16_MODBUS_PARAMETERS;
+        params.poly = 0x18005;
+        let res = CrcEngine::new(params);
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+        params.width = 0;
+        assert!(CrcEngine::new(params).is_err());
+    }
 }
@@ -0,0 +1,308 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hash::adler32::Adler32Hasher;
+use crate::hash::blake2::{Blake2b512Hasher, Blake2s256Hasher};
+use crate::hash::blake3::Blake3Hasher;
+use crate::hash::crc::{
+    Crc16CcittFalseHasher, Crc16CcittHasher, Crc16ModbusHasher, Crc32Bzip2Hasher, Crc32Hasher,
+    Crc32cHasher, Crc64EcmaHasher, Crc64XzHasher,
+};
+use crate::hash::fnv::{Fnv1a32Hasher, Fnv1a64Hasher};
+#[cfg(feature = "md5")]
+use crate::hash::md5::Md5Hasher;
+use crate::hash::murmur3::{Murmur3_128Hasher, Murmur3_32Hasher};
+use crate::hash::ripemd160::Ripemd160Hasher;
+use crate::hash::sha1::Sha1Hasher;
+use crate::hash::sha224::Sha224Hasher;
+use crate::hash::sha256::Sha256Hasher;
+use crate::hash::sha3::{
+    Keccak256Hasher, Sha3_224Hasher, Sha3_256Hasher, Sha3_384Hasher, Sha3_512Hasher,
+    Shake128Hasher, Shake256Hasher, SHAKE128_DEFAULT_OUTPUT_LENGTH, SHAKE256_DEFAULT_OUTPUT_LENGTH,
+};
+use crate::hash::sha384::Sha384Hasher;
+use crate::hash::sha512::Sha512Hasher;
+use crate::hash::sm3::Sm3Hasher;
+use crate::hash::xxhash::{Xxh32Hasher, Xxh3Hasher, Xxh64Hasher};
+use crate::hash::{DynResettableHasher, HashAlgorithm, HasherTrait};
+use crate::*;
+
+/// MD5的输出长度，未启用md5特性时也需要用到
+const MD5_OUTPUT_LENGTH: usize = 16;
+
+impl HashAlgorithm {
+    /// 所有的哈希算法，包括当前构建中未启用的
+    pub const ALL: [HashAlgorithm; 34] = [
+        HashAlgorithm::Crc32,
+        HashAlgorithm::Crc32c,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Sha3_224,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Sha3_384,
+        HashAlgorithm::Sha3_512,
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Shake128,
+        HashAlgorithm::Shake256,
+        HashAlgorithm::Blake2b512,
+        HashAlgorithm::Blake2s256,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Sm3,
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha224,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Ripemd160,
+        HashAlgorithm::Crc16Ccitt,
+        HashAlgorithm::Crc16CcittFalse,
+        HashAlgorithm::Crc16Modbus,
+        HashAlgorithm::Crc32Bzip2,
+        HashAlgorithm::Crc64Ecma,
+        HashAlgorithm::Crc64Xz,
+        HashAlgorithm::Xxh32,
+        HashAlgorithm::Xxh64,
+        HashAlgorithm::Xxh3,
+        HashAlgorithm::Adler32,
+        HashAlgorithm::Fnv1a32,
+        HashAlgorithm::Fnv1a64,
+        HashAlgorithm::Murmur3_32,
+        HashAlgorithm::Murmur3_128,
+    ];
+
+    /// 当前构建中支持的所有哈希算法
+    pub fn supported() -> Vec<HashAlgorithm> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(HashAlgorithm::is_enabled)
+            .collect()
+    }
+
+    /// 当前构建中是否启用了该算法
+    pub fn is_enabled(&self) -> bool {
+        *self != HashAlgorithm::Md5 || cfg!(feature = "md5")
+    }
+
+    /// 算法的名称，同时也是from_str接受的规范名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Crc32 => "crc32",
+            HashAlgorithm::Crc32c => "crc32c",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Sha3_224 => "sha3-224",
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            HashAlgorithm::Sha3_384 => "sha3-384",
+            HashAlgorithm::Sha3_512 => "sha3-512",
+            HashAlgorithm::Keccak256 => "keccak256",
+            HashAlgorithm::Shake128 => "shake128",
+            HashAlgorithm::Shake256 => "shake256",
+            HashAlgorithm::Blake2b512 => "blake2b512",
+            HashAlgorithm::Blake2s256 => "blake2s256",
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sm3 => "sm3",
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha224 => "sha224",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Ripemd160 => "ripemd160",
+            HashAlgorithm::Crc16Ccitt => "crc16-ccitt",
+            HashAlgorithm::Crc16CcittFalse => "crc16-ccitt-false",
+            HashAlgorithm::Crc16Modbus => "crc16-modbus",
+            HashAlgorithm::Crc32Bzip2 => "crc32-bzip2",
+            HashAlgorithm::Crc64Ecma => "crc64-ecma",
+            HashAlgorithm::Crc64Xz => "crc64-xz",
+            HashAlgorithm::Xxh32 => "xxh32",
+            HashAlgorithm::Xxh64 => "xxh64",
+            HashAlgorithm::Xxh3 => "xxh3",
+            HashAlgorithm::Adler32 => "adler32",
+            HashAlgorithm::Fnv1a32 => "fnv1a32",
+            HashAlgorithm::Fnv1a64 => "fnv1a64",
+            HashAlgorithm::Murmur3_32 => "murmur3-32",
+            HashAlgorithm::Murmur3_128 => "murmur3-128",
+        }
+    }
+
+    /// 结果的长度
+    pub fn output_length(&self) -> usize {
+        match self {
+            HashAlgorithm::Crc32 => Crc32Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc32c => Crc32cHasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha1 => Sha1Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha256 => Sha256Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha512 => Sha512Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha3_224 => Sha3_224Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha3_256 => Sha3_256Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha3_384 => Sha3_384Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha3_512 => Sha3_512Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Keccak256 => Keccak256Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Shake128 => <Shake128Hasher as HasherTrait>::OUTPUT_LENGTH,
+            HashAlgorithm::Shake256 => <Shake256Hasher as HasherTrait>::OUTPUT_LENGTH,
+            HashAlgorithm::Blake2b512 => Blake2b512Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Blake2s256 => Blake2s256Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Blake3 => Blake3Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sm3 => Sm3Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Md5 => MD5_OUTPUT_LENGTH,
+            HashAlgorithm::Sha224 => Sha224Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Sha384 => Sha384Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Ripemd160 => Ripemd160Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc16Ccitt => Crc16CcittHasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc16CcittFalse => Crc16CcittFalseHasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc16Modbus => Crc16ModbusHasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc32Bzip2 => Crc32Bzip2Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc64Ecma => Crc64EcmaHasher::OUTPUT_LENGTH,
+            HashAlgorithm::Crc64Xz => Crc64XzHasher::OUTPUT_LENGTH,
+            HashAlgorithm::Xxh32 => Xxh32Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Xxh64 => Xxh64Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Xxh3 => Xxh3Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Adler32 => Adler32Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Fnv1a32 => Fnv1a32Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Fnv1a64 => Fnv1a64Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Murmur3_32 => Murmur3_32Hasher::OUTPUT_LENGTH,
+            HashAlgorithm::Murmur3_128 => Murmur3_128Hasher::OUTPUT_LENGTH,
+        }
+    }
+
+    /// 创建一个使用默认参数（不带密钥、默认种子）的哈希操作
+    pub fn new_hasher(&self) -> Result<Box<dyn DynResettableHasher>> {
+        Ok(match self {
+            HashAlgorithm::Crc32 => Box::new(Crc32Hasher::default()),
+            HashAlgorithm::Crc32c => Box::new(Crc32cHasher::default()),
+            HashAlgorithm::Sha1 => Box::new(Sha1Hasher::default()),
+            HashAlgorithm::Sha256 => Box::new(Sha256Hasher::default()),
+            HashAlgorithm::Sha512 => Box::new(Sha512Hasher::default()),
+            HashAlgorithm::Sha3_224 => Box::new(Sha3_224Hasher::default()),
+            HashAlgorithm::Sha3_256 => Box::new(Sha3_256Hasher::default()),
+            HashAlgorithm::Sha3_384 => Box::new(Sha3_384Hasher::default()),
+            HashAlgorithm::Sha3_512 => Box::new(Sha3_512Hasher::default()),
+            HashAlgorithm::Keccak256 => Box::new(Keccak256Hasher::default()),
+            HashAlgorithm::Shake128 => {
+                Box::new(Shake128Hasher::<SHAKE128_DEFAULT_OUTPUT_LENGTH>::default())
+            }
+            HashAlgorithm::Shake256 => {
+                Box::new(Shake256Hasher::<SHAKE256_DEFAULT_OUTPUT_LENGTH>::default())
+            }
+            HashAlgorithm::Blake2b512 => Box::new(Blake2b512Hasher::default()),
+            HashAlgorithm::Blake2s256 => Box::new(Blake2s256Hasher::default()),
+            HashAlgorithm::Blake3 => Box::new(Blake3Hasher::default()),
+            HashAlgorithm::Sm3 => Box::new(Sm3Hasher::default()),
+            #[cfg(feature = "md5")]
+            HashAlgorithm::Md5 => Box::new(Md5Hasher::default()),
+            #[cfg(not(feature = "md5"))]
+            HashAlgorithm::Md5 => {
+                return Err(HASH_ALGORITHM_NOT_ENABLED.add_opt_mess("MD5需要启用md5特性"))
+            }
+            HashAlgorithm::Sha224 => Box::new(Sha224Hasher::default()),
+            HashAlgorithm::Sha384 => Box::new(Sha384Hasher::default()),
+            HashAlgorithm::Ripemd160 => Box::new(Ripemd160Hasher::default()),
+            HashAlgorithm::Crc16Ccitt => Box::new(Crc16CcittHasher::default()),
+            HashAlgorithm::Crc16CcittFalse => Box::new(Crc16CcittFalseHasher::default()),
+            HashAlgorithm::Crc16Modbus => Box::new(Crc16ModbusHasher::default()),
+            HashAlgorithm::Crc32Bzip2 => Box::new(Crc32Bzip2Hasher::default()),
+            HashAlgorithm::Crc64Ecma => Box::new(Crc64EcmaHasher::default()),
+            HashAlgorithm::Crc64Xz => Box::new(Crc64XzHasher::default()),
+            HashAlgorithm::Xxh32 => Box::new(Xxh32Hasher::default()),
+            HashAlgorithm::Xxh64 => Box::new(Xxh64Hasher::default()),
+            HashAlgorithm::Xxh3 => Box::new(Xxh3Hasher::default()),
+            HashAlgorithm::Adler32 => Box::new(Adler32Hasher::default()),
+            HashAlgorithm::Fnv1a32 => Box::new(Fnv1a32Hasher::default()),
+            HashAlgorithm::Fnv1a64 => Box::new(Fnv1a64Hasher::default()),
+            HashAlgorithm::Murmur3_32 => Box::new(Murmur3_32Hasher::default()),
+            HashAlgorithm::Murmur3_128 => Box::new(Murmur3_128Hasher::default()),
+        })
+    }
+}
+
+/// 规范化算法名称：转为小写并去掉'-'和'_'，从而"SHA-256"、"sha_256"和"sha256"等价
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = CiftlError;
+
+    /// 从名称解析哈希算法，忽略大小写以及'-'和'_'
+    fn from_str(s: &str) -> Result<Self> {
+        let name = normalize_name(s);
+        HashAlgorithm::ALL
+            .iter()
+            .find(|algorithm| normalize_name(algorithm.name()) == name)
+            .copied()
+            .ok_or_else(|| UNKNOWN_HASH_ALGORITHM.add_opt_mess(s))
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 根据名称创建一个哈希操作
+pub fn new_hasher_by_name(name: &str) -> Result<Box<dyn DynResettableHasher>> {
+    name.parse::<HashAlgorithm>()?.new_hasher()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn test_hash_algorithm_metadata() {
+        for algorithm in HashAlgorithm::ALL {
+            // 名称可以解析回原算法，且不与其他算法冲突
+            assert_eq!(
+                algorithm.name().parse::<HashAlgorithm>().unwrap(),
+                algorithm
+            );
+            assert_eq!(algorithm.to_string(), algorithm.name());
+            // ALL中的值与枚举的值一一对应
+            assert_eq!(HashAlgorithm::from_u8(algorithm as u8).unwrap(), algorithm);
+        }
+        for algorithm in HashAlgorithm::supported() {
+            let mut hasher = algorithm.new_hasher().unwrap();
+            hasher.update_message("123456");
+            assert_eq!(hasher.output_length(), algorithm.output_length());
+            assert_eq!(hasher.finalize().len(), algorithm.output_length());
+        }
+        assert_eq!(
+            HashAlgorithm::supported().contains(&HashAlgorithm::Md5),
+            cfg!(feature = "md5")
+        );
+        assert_eq!(HashAlgorithm::Md5.output_length(), 16);
+    }
+
+    #[test]
+    fn test_new_hasher_by_name() {
+        for name in ["sha256", "SHA-256", "Sha_256"] {
+            let mut hasher = new_hasher_by_name(name).unwrap();
+            hasher.update_message("123456");
+            assert_eq!(
+                HexEncoding::default().encode(&hasher.finalize()),
+                "8D969EEF6ECAD3C29A3A629280E686CF0C3F5D5A86AFF3CA12020C923ADC6C92"
+            );
+        }
+        assert_eq!(
+            "SHA3-256".parse::<HashAlgorithm>().unwrap(),
+            HashAlgorithm::Sha3_256
+        );
+        assert_eq!(
+            "crc16_modbus".parse::<HashAlgorithm>().unwrap(),
+            HashAlgorithm::Crc16Modbus
+        );
+        let res = new_hasher_by_name("sha257");
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+        if !cfg!(feature = "md5") {
+            let res = new_hasher_by_name("md5");
+            assert!(res.is_err());
+            println!("错误：{}", res.err().unwrap());
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::hash::{HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// FNV-1a 32位的输出长度
+pub const FNV1A32_OUTPUT_LENGTH: usize = 4;
+/// FNV-1a 64位的输出长度
+pub const FNV1A64_OUTPUT_LENGTH: usize = 8;
+/// FNV 32位的偏移基准，即默认的种子
+pub const FNV32_OFFSET_BASIS: u32 = 0x811C9DC5;
+/// FNV 64位的偏移基准，即默认的种子
+pub const FNV64_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+
+/// FNV 32位的素数
+const FNV32_PRIME: u32 = 0x01000193;
+/// FNV 64位的素数
+const FNV64_PRIME: u64 = 0x00000100000001B3;
+
+/// FNV-1a 32位哈希操作，非密码学哈希，结果按大端序输出
+#[derive(Clone)]
+pub struct Fnv1a32Hasher {
+    m_state: u32,
+    m_seed: u32,
+}
+
+impl Fnv1a32Hasher {
+    /// 使用种子创建，种子将代替偏移基准作为初始状态
+    pub fn with_seed(seed: u32) -> Self {
+        Fnv1a32Hasher {
+            m_state: seed,
+            m_seed: seed,
+        }
+    }
+
+    /// 获取哈希的数值
+    pub fn value(&self) -> u32 {
+        self.m_state
+    }
+}
+
+impl Default for Fnv1a32Hasher {
+    fn default() -> Self {
+        Self::with_seed(FNV32_OFFSET_BASIS)
+    }
+}
+
+impl HasherTrait for Fnv1a32Hasher {
+    const OUTPUT_LENGTH: usize = FNV1A32_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.update_bytes(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        for b in vec {
+            self.m_state = (self.m_state ^ *b as u32).wrapping_mul(FNV32_PRIME);
+        }
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.m_state.to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_state.to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Fnv1a32Hasher {
+    fn reset(&mut self) {
+        self.m_state = self.m_seed;
+    }
+}
+
+/// FNV-1a 64位哈希操作，非密码学哈希，结果按大端序输出
+#[derive(Clone)]
+pub struct Fnv1a64Hasher {
+    m_state: u64,
+    m_seed: u64,
+}
+
+impl Fnv1a64Hasher {
+    /// 使用种子创建，种子将代替偏移基准作为初始状态
+    pub fn with_seed(seed: u64) -> Self {
+        Fnv1a64Hasher {
+            m_state: seed,
+            m_seed: seed,
+        }
+    }
+
+    /// 获取哈希的数值
+    pub fn value(&self) -> u64 {
+        self.m_state
+    }
+}
+
+impl Default for Fnv1a64Hasher {
+    fn default() -> Self {
+        Self::with_seed(FNV64_OFFSET_BASIS)
+    }
+}
+
+impl HasherTrait for Fnv1a64Hasher {
+    const OUTPUT_LENGTH: usize = FNV1A64_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.update_bytes(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        for b in vec {
+            self.m_state = (self.m_state ^ *b as u64).wrapping_mul(FNV64_PRIME);
+        }
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.m_state.to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_state.to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Fnv1a64Hasher {
+    fn reset(&mut self) {
+        self.m_state = self.m_seed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        let cases = [
+            ("", 0x811C9DC5, 0xCBF29CE484222325),
+            ("a", 0xE40C292C, 0xAF63DC4C8601EC8C),
+            ("foobar", 0xBF9CF968, 0x85944171F73967E8),
+        ];
+        for (message, expected32, expected64) in cases {
+            let mut fnv32_hasher = Fnv1a32Hasher::default();
+            let mut fnv64_hasher = Fnv1a64Hasher::default();
+            fnv32_hasher.update_message(message);
+            fnv64_hasher.update_message(message);
+            assert_eq!(fnv32_hasher.value(), expected32);
+            assert_eq!(fnv64_hasher.value(), expected64);
+        }
+        // 带种子
+        let mut fnv32_hasher = Fnv1a32Hasher::with_seed(12345);
+        let mut fnv64_hasher = Fnv1a64Hasher::with_seed(12345);
+        fnv32_hasher.update_message("foobar");
+        fnv64_hasher.update_message("foobar");
+        assert_eq!(fnv32_hasher.finalize(), vec![0xA3, 0x51, 0xED, 0x04]);
+        assert_eq!(fnv64_hasher.value(), 0x7D565BB949B60E24);
+    }
+}
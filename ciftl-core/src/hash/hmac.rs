@@ -0,0 +1,224 @@
+use crate::hash::sha1::Sha1Hasher;
+use crate::hash::sha256::Sha256Hasher;
+use crate::hash::sha512::Sha512Hasher;
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// HMAC内层填充使用的字节
+const HMAC_IPAD: u8 = 0x36;
+/// HMAC外层填充使用的字节
+const HMAC_OPAD: u8 = 0x5C;
+/// finalize_into中存放内层结果的栈上缓冲区的长度，足以容纳本库中所有分组哈希算法的结果
+const HMAC_INNER_BUFFER_LENGTH: usize = 64;
+
+/// HMAC（RFC 2104），可以基于任意以分组处理消息的哈希算法
+/// 长于分组长度的密钥会先进行一次哈希，短于分组长度的密钥在末尾补0
+pub struct Hmac<HR: BlockHasherTrait + Default> {
+    /// 已经输入了内层填充密钥的哈希操作
+    m_inner: HR,
+    /// 内层填充密钥，用于重置
+    m_ipad_key: ByteVector,
+    /// 外层填充密钥
+    m_opad_key: ByteVector,
+}
+
+/// HMAC-SHA1
+pub type HmacSha1 = Hmac<Sha1Hasher>;
+/// HMAC-SHA256
+pub type HmacSha256 = Hmac<Sha256Hasher>;
+/// HMAC-SHA512
+pub type HmacSha512 = Hmac<Sha512Hasher>;
+
+impl<HR: BlockHasherTrait + Default> Hmac<HR> {
+    /// 使用密钥创建一个HMAC操作
+    pub fn new(key: &[u8]) -> Self {
+        // 将密钥调整为分组长度
+        let mut block_key = if key.len() > HR::BLOCK_LENGTH {
+            let mut c = HR::default();
+            c.update_bytes(key);
+            c.finalize()
+        } else {
+            key.to_vec()
+        };
+        block_key.resize(HR::BLOCK_LENGTH, 0x00);
+        let ipad_key: ByteVector = block_key.iter().map(|b| b ^ HMAC_IPAD).collect();
+        let opad_key: ByteVector = block_key.iter().map(|b| b ^ HMAC_OPAD).collect();
+        let mut inner = HR::default();
+        inner.update_bytes(&ipad_key);
+        Hmac::<HR> {
+            m_inner: inner,
+            m_ipad_key: ipad_key,
+            m_opad_key: opad_key,
+        }
+    }
+
+    /// 以常量时间比较当前结果与给定的认证码
+    pub fn verify(&self, tag: &[u8]) -> bool {
+        let res = self.finalize();
+        if res.len() != tag.len() {
+            return false;
+        }
+        res.iter().zip(tag).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+}
+
+impl<HR: BlockHasherTrait + Default> HasherTrait for Hmac<HR> {
+    const OUTPUT_LENGTH: usize = HR::OUTPUT_LENGTH;
+
+    /// 计算一个消息的认证码
+    fn update_message(&mut self, message: &str) {
+        self.m_inner.update_message(message);
+    }
+    /// 计算一个字节数组的认证码
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_inner.update_bytes(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        let mut res = vec![0x00; HR::OUTPUT_LENGTH];
+        self.finalize_into(&mut res);
+        res
+    }
+    /// 将结果写入out，内层结果存放在栈上，不会分配内存（结果长于缓冲区的哈希算法除外）
+    fn finalize_into(&self, out: &mut [u8]) {
+        let mut outer = HR::default();
+        outer.update_bytes(&self.m_opad_key);
+        if HR::OUTPUT_LENGTH <= HMAC_INNER_BUFFER_LENGTH {
+            let mut buffer = [0x00; HMAC_INNER_BUFFER_LENGTH];
+            let inner_res = &mut buffer[..HR::OUTPUT_LENGTH];
+            self.m_inner.finalize_into(inner_res);
+            outer.update_bytes(inner_res);
+        } else {
+            outer.update_bytes(&self.m_inner.finalize());
+        }
+        outer.finalize_into(out);
+    }
+}
+
+impl<HR: BlockHasherTrait + Default> ResettableHasherTrait for Hmac<HR> {
+    /// 恢复到刚创建时的状态，密钥保持不变
+    fn reset(&mut self) {
+        self.m_inner = HR::default();
+        self.m_inner.update_bytes(&self.m_ipad_key);
+    }
+}
+
+impl<HR: BlockHasherTrait + Default> BlockHasherTrait for Hmac<HR> {
+    const BLOCK_LENGTH: usize = HR::BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use crate::hash::sha512::SHA512_OUTPUT_LENGTH;
+
+    fn hmac_hex<HR: BlockHasherTrait + Default>(key: &[u8], data: &[u8]) -> String {
+        let mut hmac = Hmac::<HR>::new(key);
+        hmac.update_bytes(data);
+        HexEncoding::default().encode(&hmac.finalize())
+    }
+
+    /// RFC 2202和RFC 4231中共用的测试用例（不含截断输出的用例）
+    fn test_cases(long_key_length: usize) -> Vec<(ByteVector, ByteVector)> {
+        vec![
+            (vec![0x0B; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xAA; 20], vec![0xDD; 50]),
+            ((0x01..=0x19).collect(), vec![0xCD; 50]),
+            (
+                vec![0xAA; long_key_length],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_hmac_sha1() {
+        // RFC 2202 3. Test Cases for HMAC-SHA-1
+        let mut cases = test_cases(80);
+        cases.push((
+            vec![0xAA; 80],
+            b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data".to_vec(),
+        ));
+        let expected = [
+            "B617318655057264E28BC0B6FB378C8EF146BE00",
+            "EFFCDF6AE5EB2FA2D27416D5F184DF9C259A7C79",
+            "125D7342B9AC11CD91A39AF48AA17B4F63F175D3",
+            "4C9007F4026250C6BC8414F9BF50C86C2D7235DA",
+            "AA4AE5E15272D00E95705637CE8A3B55ED402112",
+            "E8E99D0F45237D786D6BBAA7965C7808BBFF1A91",
+        ];
+        for ((key, data), expected) in cases.iter().zip(expected) {
+            assert_eq!(hmac_hex::<Sha1Hasher>(key, data), expected);
+        }
+    }
+
+    fn rfc4231_cases() -> Vec<(ByteVector, ByteVector)> {
+        let mut cases = test_cases(131);
+        cases.push((vec![0xAA; 131], b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.".to_vec()));
+        cases
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 4. Test Vectors，用例1、2、3、4、6、7
+        let expected = [
+            "B0344C61D8DB38535CA8AFCEAF0BF12B881DC200C9833DA726E9376C2E32CFF7",
+            "5BDCC146BF60754E6A042426089575C75A003F089D2739839DEC58B964EC3843",
+            "773EA91E36800E46854DB8EBD09181A72959098B3EF8C122D9635514CED565FE",
+            "82558A389A443C0EA4CC819899F2083A85F0FAA3E578F8077A2E3FF46729665B",
+            "60E431591EE0B67F0D8A26AACBF5B77F8E0BC6213728C5140546040F0EE37F54",
+            "9B09FFA71B942FCB27635FBCD5B0E944BFDC63644F0713938A7F51535C3A35E2",
+        ];
+        for ((key, data), expected) in rfc4231_cases().iter().zip(expected) {
+            assert_eq!(hmac_hex::<Sha256Hasher>(key, data), expected);
+        }
+    }
+
+    #[test]
+    fn test_hmac_sha512() {
+        // RFC 4231 4. Test Vectors，用例1、2、3、4、6、7
+        let expected = [
+            "87AA7CDEA5EF619D4FF0B4241A1D6CB02379F4E2CE4EC2787AD0B30545E17CDEDAA833B7D6B8A702038B274EAEA3F4E4BE9D914EEB61F1702E696C203A126854",
+            "164B7A7BFCF819E2E395FBE73B56E0A387BD64222E831FD610270CD7EA2505549758BF75C05A994A6D034F65F8F0E6FDCAEAB1A34D4A6B4B636E070A38BCE737",
+            "FA73B0089D56A284EFB0F0756C890BE9B1B5DBDD8EE81A3655F83E33B2279D39BF3E848279A722C806B485A47E67C807B946A337BEE8942674278859E13292FB",
+            "B0BA465637458C6990E5A8C5F61D4AF7E576D97FF94B872DE76F8050361EE3DBA91CA5C11AA25EB4D679275CC5788063A5F19741120C4F2DE2ADEBEB10A298DD",
+            "80B24263C7C1A3EBB71493C1DD7BE8B49B46D1F41B4AEEC1121B013783F8F3526B56D037E05F2598BD0FD2215D6A1E5295E64F73F63F0AEC8B915A985D786598",
+            "E37B6A775DC87DBAA4DFA9F96E5E3FFDDEBD71F8867289865DF5A32D20CDC944B6022CAC3C4982B10D5EEB55C3E4DE15134676FB6DE0446065C97440FA8C6A58",
+        ];
+        for ((key, data), expected) in rfc4231_cases().iter().zip(expected) {
+            assert_eq!(hmac_hex::<Sha512Hasher>(key, data), expected);
+        }
+    }
+
+    #[test]
+    fn test_hmac_update_and_verify() {
+        // 分段输入与一次输入的结果相同，finalize之后可以继续输入
+        let mut hmac = HmacSha256::new(b"Jefe");
+        hmac.update_message("what do ya want ");
+        let _ = hmac.finalize();
+        hmac.update_message("for nothing?");
+        let tag = hmac.finalize();
+        assert_eq!(
+            HexEncoding::default().encode(&tag),
+            "5BDCC146BF60754E6A042426089575C75A003F089D2739839DEC58B964EC3843"
+        );
+        assert!(hmac.verify(&tag));
+        let mut bad_tag = tag.clone();
+        bad_tag[0] ^= 0x01;
+        assert!(!hmac.verify(&bad_tag));
+        assert!(!hmac.verify(&tag[..16]));
+        // finalize_into与finalize的结果相同
+        let mut out = [0x00; 32];
+        hmac.finalize_into(&mut out);
+        assert_eq!(out.as_slice(), tag.as_slice());
+        let mut hmac = HmacSha512::new(b"Jefe");
+        hmac.update_message("what do ya want for nothing?");
+        assert_eq!(
+            hmac.finalize_array::<SHA512_OUTPUT_LENGTH>().as_ref(),
+            hmac.finalize().as_slice()
+        );
+    }
+}
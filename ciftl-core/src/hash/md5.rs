@@ -0,0 +1,70 @@
+use ::md5::{Digest, Md5 as ExMd5};
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// Md5的输出长度
+pub const MD5_OUTPUT_LENGTH: usize = 16;
+/// Md5的分组长度
+pub const MD5_BLOCK_LENGTH: usize = 64;
+
+/// Md5哈希操作，Md5已不再安全，仅用于校验旧系统产生的摘要
+#[derive(Clone, Default)]
+pub struct Md5Hasher {
+    m_ctx: ExMd5,
+}
+
+impl HasherTrait for Md5Hasher {
+    const OUTPUT_LENGTH: usize = MD5_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.clone().finalize().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_ctx.clone().finalize());
+    }
+}
+
+impl ResettableHasherTrait for Md5Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        Digest::reset(&mut self.m_ctx);
+    }
+}
+
+impl BlockHasherTrait for Md5Hasher {
+    const BLOCK_LENGTH: usize = MD5_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_md5_operation() {
+        let hexe = HexEncoding::default();
+        let mut md5_hasher = Md5Hasher::default();
+        assert_eq!(
+            "D41D8CD98F00B204E9800998ECF8427E".to_string(),
+            hexe.encode(&md5_hasher.finalize())
+        );
+        // 123456
+        md5_hasher.update_message("123456");
+        assert_eq!(
+            "E10ADC3949BA59ABBE56E057F20F883E".to_string(),
+            hexe.encode(&md5_hasher.finalize())
+        );
+    }
+}
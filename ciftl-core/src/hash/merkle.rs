@@ -0,0 +1,393 @@
+use std::io::{self, Read, Write};
+use std::marker;
+
+use crate::hash::HasherTrait;
+use crate::*;
+
+/// 默认的数据块长度
+pub const MERKLE_DEFAULT_CHUNK_LENGTH: usize = 64 * 1024;
+/// 叶子节点哈希的前缀（RFC 6962 2.1）
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+/// 内部节点哈希的前缀（RFC 6962 2.1）
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// 计算叶子节点的哈希值，即HASH(0x00 || data)
+pub fn merkle_leaf_hash<HR: HasherTrait + Default>(data: &[u8]) -> ByteVector {
+    let mut c = HR::default();
+    HasherTrait::update_bytes(&mut c, &[MERKLE_LEAF_PREFIX]);
+    HasherTrait::update_bytes(&mut c, data);
+    HasherTrait::finalize(&c)
+}
+
+/// 计算内部节点的哈希值，即HASH(0x01 || left || right)
+pub fn merkle_node_hash<HR: HasherTrait + Default>(left: &[u8], right: &[u8]) -> ByteVector {
+    let mut c = HR::default();
+    HasherTrait::update_bytes(&mut c, &[MERKLE_NODE_PREFIX]);
+    HasherTrait::update_bytes(&mut c, left);
+    HasherTrait::update_bytes(&mut c, right);
+    HasherTrait::finalize(&c)
+}
+
+/// 自底向上计算各层节点，第0层为叶子节点
+/// 每层两两合并，节点数为奇数时最后一个节点直接提升到上一层，与RFC 6962按2的幂切分的定义等价
+fn build_levels<HR: HasherTrait + Default>(leaf_hashes: Vec<ByteVector>) -> Vec<Vec<ByteVector>> {
+    let mut levels = vec![leaf_hashes];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = &levels[levels.len() - 1];
+        let next = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => merkle_node_hash::<HR>(left, right),
+                _ => pair[0].clone(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// 默克尔树（RFC 6962），数据按固定长度切分为数据块，每个数据块是一个叶子节点
+/// 不保存数据块本身，只缓存各层节点的哈希值（约为叶子节点数的2倍），因此可以用于很大的数据
+#[derive(Clone)]
+pub struct MerkleTree<HR: HasherTrait + Default> {
+    /// 数据块的长度
+    m_chunk_length: usize,
+    /// 各层节点的哈希值，第0层为叶子节点，最后一层为根
+    m_levels: Vec<Vec<ByteVector>>,
+    _hr: marker::PhantomData<HR>,
+}
+
+/// 包含性证明，用于证明一个数据块属于某个根
+#[derive(Clone, PartialEq, Debug)]
+pub struct MerkleProof {
+    /// 数据块的序号
+    pub leaf_index: u64,
+    /// 树中叶子节点的总数
+    pub tree_size: u64,
+    /// 审计路径，从叶子到根的顺序
+    pub path: Vec<ByteVector>,
+}
+
+impl<HR: HasherTrait + Default> MerkleTree<HR> {
+    /// 从已经计算好的叶子节点哈希值创建默克尔树
+    pub fn from_leaf_hashes(chunk_length: usize, leaf_hashes: Vec<ByteVector>) -> Result<Self> {
+        if chunk_length == 0 {
+            return Err(INVALID_MERKLE_CHUNK_LENGTH.clone());
+        }
+        Ok(MerkleTree::<HR> {
+            m_chunk_length: chunk_length,
+            m_levels: build_levels::<HR>(leaf_hashes),
+            _hr: marker::PhantomData::<HR>,
+        })
+    }
+
+    /// 计算一段数据的默克尔树
+    pub fn from_bytes(chunk_length: usize, data: &[u8]) -> Result<Self> {
+        let mut builder = MerkleTreeBuilder::<HR>::new(chunk_length)?;
+        builder.update_bytes(data);
+        Ok(builder.finalize())
+    }
+
+    /// 计算reader中剩余的全部数据的默克尔树
+    pub fn from_reader<R: Read + ?Sized>(chunk_length: usize, reader: &mut R) -> Result<Self> {
+        let mut builder = MerkleTreeBuilder::<HR>::new(chunk_length)?;
+        builder.update_reader(reader)?;
+        Ok(builder.finalize())
+    }
+
+    /// 数据块的长度
+    pub fn chunk_length(&self) -> usize {
+        self.m_chunk_length
+    }
+
+    /// 叶子节点的数量
+    pub fn leaf_count(&self) -> usize {
+        self.m_levels[0].len()
+    }
+
+    /// 所有叶子节点的哈希值
+    pub fn leaf_hashes(&self) -> &[ByteVector] {
+        &self.m_levels[0]
+    }
+
+    /// 根的哈希值，没有叶子节点时为空数据的哈希值
+    pub fn root(&self) -> ByteVector {
+        if self.m_levels[0].is_empty() {
+            return HasherTrait::finalize(&HR::default());
+        }
+        self.m_levels[self.m_levels.len() - 1][0].clone()
+    }
+
+    /// 生成第index个数据块的包含性证明（RFC 6962 2.1.1）
+    pub fn proof(&self, index: usize) -> Result<MerkleProof> {
+        if index >= self.leaf_count() {
+            return Err(MERKLE_LEAF_INDEX_OUT_OF_RANGE.add_opt_mess(&format!(
+                "序号为{}，叶子节点的数量为{}",
+                index,
+                self.leaf_count()
+            )));
+        }
+        // 审计路径从叶子到根，依次为每层中兄弟节点的哈希值，被提升的节点没有兄弟节点
+        let mut path = Vec::new();
+        let mut i = index;
+        for level in &self.m_levels[..self.m_levels.len() - 1] {
+            if let Some(sibling) = level.get(i ^ 1) {
+                path.push(sibling.clone());
+            }
+            i >>= 1;
+        }
+        Ok(MerkleProof {
+            leaf_index: index as u64,
+            tree_size: self.leaf_count() as u64,
+            path,
+        })
+    }
+}
+
+impl MerkleProof {
+    /// 验证数据块属于根为root的默克尔树
+    pub fn verify<HR: HasherTrait + Default>(&self, chunk: &[u8], root: &[u8]) -> bool {
+        self.verify_leaf_hash::<HR>(&merkle_leaf_hash::<HR>(chunk), root)
+    }
+
+    /// 验证叶子节点哈希值属于根为root的默克尔树（RFC 9162 2.1.3.2）
+    pub fn verify_leaf_hash<HR: HasherTrait + Default>(
+        &self,
+        leaf_hash: &[u8],
+        root: &[u8],
+    ) -> bool {
+        if self.leaf_index >= self.tree_size {
+            return false;
+        }
+        let mut fn_ = self.leaf_index;
+        let mut sn = self.tree_size - 1;
+        let mut r = leaf_hash.to_vec();
+        for p in &self.path {
+            if sn == 0 {
+                return false;
+            }
+            if fn_ & 1 == 1 || fn_ == sn {
+                r = merkle_node_hash::<HR>(p, &r);
+                while fn_ & 1 == 0 && fn_ != 0 {
+                    fn_ >>= 1;
+                    sn >>= 1;
+                }
+            } else {
+                r = merkle_node_hash::<HR>(&r, p);
+            }
+            fn_ >>= 1;
+            sn >>= 1;
+        }
+        sn == 0 && r == root
+    }
+}
+
+/// 以流的形式输入数据并构建默克尔树，只缓存一个数据块
+pub struct MerkleTreeBuilder<HR: HasherTrait + Default> {
+    /// 数据块的长度
+    m_chunk_length: usize,
+    /// 尚未凑满一个数据块的数据
+    m_buffer: ByteVector,
+    /// 已经完成的叶子节点的哈希值
+    m_leaf_hashes: Vec<ByteVector>,
+    _hr: marker::PhantomData<HR>,
+}
+
+impl<HR: HasherTrait + Default> MerkleTreeBuilder<HR> {
+    /// 使用指定的数据块长度创建一个构建器
+    pub fn new(chunk_length: usize) -> Result<Self> {
+        if chunk_length == 0 {
+            return Err(INVALID_MERKLE_CHUNK_LENGTH.clone());
+        }
+        Ok(MerkleTreeBuilder::<HR> {
+            m_chunk_length: chunk_length,
+            m_buffer: Vec::new(),
+            m_leaf_hashes: Vec::new(),
+            _hr: marker::PhantomData::<HR>,
+        })
+    }
+
+    /// 输入一个字节数组
+    pub fn update_bytes(&mut self, vec: &[u8]) {
+        let mut data = vec;
+        // 先补齐缓冲区中的数据块
+        if !self.m_buffer.is_empty() {
+            let n = (self.m_chunk_length - self.m_buffer.len()).min(data.len());
+            self.m_buffer.extend_from_slice(&data[..n]);
+            data = &data[n..];
+            if self.m_buffer.len() < self.m_chunk_length {
+                return;
+            }
+            self.m_leaf_hashes
+                .push(merkle_leaf_hash::<HR>(&self.m_buffer));
+            self.m_buffer.clear();
+        }
+        let mut chunks = data.chunks_exact(self.m_chunk_length);
+        for chunk in &mut chunks {
+            self.m_leaf_hashes.push(merkle_leaf_hash::<HR>(chunk));
+        }
+        self.m_buffer.extend_from_slice(chunks.remainder());
+    }
+
+    /// 输入reader中剩余的全部数据
+    pub fn update_reader<R: Read + ?Sized>(&mut self, reader: &mut R) -> Result<u64> {
+        io::copy(reader, self)
+            .map_err(|e| FAILED_WHEN_READING_HASH_INPUT.add_opt_mess(&format!("{e}")))
+    }
+
+    /// 完成构建，最后一个不满的数据块也作为一个叶子节点
+    pub fn finalize(mut self) -> MerkleTree<HR> {
+        if !self.m_buffer.is_empty() {
+            self.m_leaf_hashes
+                .push(merkle_leaf_hash::<HR>(&self.m_buffer));
+        }
+        MerkleTree::<HR> {
+            m_chunk_length: self.m_chunk_length,
+            m_levels: build_levels::<HR>(self.m_leaf_hashes),
+            _hr: marker::PhantomData::<HR>,
+        }
+    }
+}
+
+/// 以io::Write的形式输入数据，从而可以使用io::copy等函数
+impl<HR: HasherTrait + Default> Write for MerkleTreeBuilder<HR> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use crate::hash::sha256::Sha256Hasher;
+    use crate::hash::sm3::Sm3Hasher;
+    use std::io::Cursor;
+
+    /// RFC 6962参考实现中使用的测试数据
+    fn test_leaves() -> Vec<ByteVector> {
+        vec![
+            vec![],
+            vec![0x00],
+            vec![0x10],
+            vec![0x20, 0x21],
+            vec![0x30, 0x31],
+            vec![0x40, 0x41, 0x42, 0x43],
+            (0x50..0x58).collect(),
+            (0x60..0x70).collect(),
+        ]
+    }
+
+    fn test_tree(n: usize) -> MerkleTree<Sha256Hasher> {
+        let leaf_hashes = test_leaves()[..n]
+            .iter()
+            .map(|leaf| merkle_leaf_hash::<Sha256Hasher>(leaf))
+            .collect();
+        MerkleTree::from_leaf_hashes(MERKLE_DEFAULT_CHUNK_LENGTH, leaf_hashes).unwrap()
+    }
+
+    #[test]
+    fn test_merkle_root() {
+        let hexe = HexEncoding::default();
+        assert_eq!(
+            hexe.encode(&test_tree(0).root()),
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+        );
+        assert_eq!(
+            hexe.encode(&test_tree(1).root()),
+            "6E340B9CFFB37A989CA544E6BB780A2C78901D3FB33738768511A30617AFA01D"
+        );
+        assert_eq!(
+            hexe.encode(&test_tree(5).root()),
+            "4E3BBB1F7B478DCFE71FB631631519A3BCA12C9AEFCA1612BFCE4C13A86264D4"
+        );
+        assert_eq!(
+            hexe.encode(&test_tree(8).root()),
+            "5DC9DA79A70659A9AD559CB701DED9A2AB9D823AAD2F4960CFE370EFF4604328"
+        );
+        // 审计路径
+        let proof = test_tree(8).proof(5).unwrap();
+        assert_eq!(
+            proof
+                .path
+                .iter()
+                .map(|p| hexe.encode(p))
+                .collect::<Vec<_>>(),
+            vec![
+                "BC1A0643B12E4D2D7C77918F44E0F4F79A838B6CF9EC5B5C283E1F4D88599E6B",
+                "CA854EA128ED050B41B35FFC1B87B8EB2BDE461E9E3B5596ECE6B9D5975A0AE0",
+                "D37EE418976DD95753C1C73862B9398FA2A2CF9B4FF0FDFE8B30CD95209614B7",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merkle_proof() {
+        let data: ByteVector = (0..1000u32).map(|i| (i * 31) as u8).collect();
+        for chunk_length in [1, 7, 64, 100, 999, 1000, 4096] {
+            let tree = MerkleTree::<Sm3Hasher>::from_bytes(chunk_length, &data).unwrap();
+            let root = tree.root();
+            let chunks: Vec<&[u8]> = data.chunks(chunk_length).collect();
+            assert_eq!(tree.leaf_count(), chunks.len());
+            for (i, chunk) in chunks.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(proof.verify::<Sm3Hasher>(chunk, &root));
+                // 数据块被修改
+                let mut tampered = chunk.to_vec();
+                tampered[0] ^= 0x01;
+                assert!(!proof.verify::<Sm3Hasher>(&tampered, &root));
+                // 序号或树的大小被修改
+                let mut wrong_proof = proof.clone();
+                wrong_proof.leaf_index ^= 1;
+                if wrong_proof.leaf_index < wrong_proof.tree_size {
+                    assert!(!wrong_proof.verify::<Sm3Hasher>(chunk, &root));
+                }
+                let mut wrong_proof = proof.clone();
+                wrong_proof.tree_size = wrong_proof.leaf_index;
+                assert!(!wrong_proof.verify::<Sm3Hasher>(chunk, &root));
+                // 审计路径被截断
+                if !proof.path.is_empty() {
+                    let mut wrong_proof = proof.clone();
+                    wrong_proof.path.pop();
+                    assert!(!wrong_proof.verify::<Sm3Hasher>(chunk, &root));
+                }
+            }
+        }
+        // 叶子节点的哈希值带有前缀，因此内部节点不能冒充数据块
+        let tree = MerkleTree::<Sha256Hasher>::from_bytes(1, b"ab").unwrap();
+        let forged = [tree.leaf_hashes()[0].clone(), tree.leaf_hashes()[1].clone()].concat();
+        let proof = MerkleProof {
+            leaf_index: 0,
+            tree_size: 1,
+            path: vec![],
+        };
+        assert!(!proof.verify::<Sha256Hasher>(&[&[0x01], &forged[..]].concat(), &tree.root()));
+    }
+
+    #[test]
+    fn test_merkle_tree_builder() {
+        let data: ByteVector = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let expected = MerkleTree::<Sha256Hasher>::from_bytes(4096, &data).unwrap();
+        let mut builder = MerkleTreeBuilder::<Sha256Hasher>::new(4096).unwrap();
+        for piece in data.chunks(333) {
+            builder.update_bytes(piece);
+        }
+        assert_eq!(builder.finalize().root(), expected.root());
+        let tree = MerkleTree::<Sha256Hasher>::from_reader(4096, &mut Cursor::new(&data)).unwrap();
+        assert_eq!(tree.root(), expected.root());
+        assert_eq!(tree.chunk_length(), 4096);
+        // 不合法的参数
+        let res = MerkleTreeBuilder::<Sha256Hasher>::new(0);
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+        let res = tree.proof(tree.leaf_count());
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+    }
+}
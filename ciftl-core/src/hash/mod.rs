@@ -1,17 +1,99 @@
+pub mod adler32;
+pub mod blake2;
+pub mod blake3;
+pub mod checksum;
 pub mod crc;
+pub mod factory;
+pub mod fnv;
+pub mod hmac;
+#[cfg(feature = "md5")]
+pub mod md5;
+pub mod merkle;
+pub mod murmur3;
+pub mod ripemd160;
 pub mod sha1;
+pub mod sha224;
 pub mod sha256;
+pub mod sha3;
+pub mod sha384;
 pub mod sha512;
+pub mod sm3;
+pub mod stream;
+pub mod xxhash;
+
+use num_derive::FromPrimitive;
 
 use crate::*;
 
-/// 所有支持的哈希算法
+/// 所有支持的哈希算法，其值同时作为密文头部中的算法标识，不应修改
+#[derive(FromPrimitive, Clone, Copy, PartialEq, Debug)]
 pub enum HashAlgorithm {
-    Crc32,
-    Crc32c,
-    Sha1,
-    Sha256,
-    Sha512,
+    Crc32 = 1,
+    Crc32c = 2,
+    Sha1 = 3,
+    Sha256 = 4,
+    Sha512 = 5,
+    Sha3_224 = 6,
+    Sha3_256 = 7,
+    Sha3_384 = 8,
+    Sha3_512 = 9,
+    Keccak256 = 10,
+    /// 输出长度为SHAKE128_DEFAULT_OUTPUT_LENGTH
+    Shake128 = 11,
+    /// 输出长度为SHAKE256_DEFAULT_OUTPUT_LENGTH
+    Shake256 = 12,
+    Blake2b512 = 13,
+    Blake2s256 = 14,
+    /// 不带密钥的BLAKE3，输出长度为BLAKE3_OUTPUT_LENGTH
+    Blake3 = 15,
+    Sm3 = 16,
+    /// 需要启用md5特性
+    Md5 = 17,
+    Sha224 = 18,
+    Sha384 = 19,
+    Ripemd160 = 20,
+    Crc16Ccitt = 21,
+    Crc16CcittFalse = 22,
+    Crc16Modbus = 23,
+    Crc32Bzip2 = 24,
+    Crc64Ecma = 25,
+    Crc64Xz = 26,
+
+    // 以下为用于缓存键、去重等场景的快速哈希算法，从101开始编号以便与上面的算法区分
+    // 它们不能抵抗恶意构造的碰撞，均使用默认的种子
+    Xxh32 = 101,
+    Xxh64 = 102,
+    Xxh3 = 103,
+    Adler32 = 104,
+    Fnv1a32 = 105,
+    Fnv1a64 = 106,
+    Murmur3_32 = 107,
+    Murmur3_128 = 108,
+}
+
+impl HashAlgorithm {
+    /// 是否为密码学哈希算法，CRC系列和快速哈希算法都不是
+    pub fn is_cryptographic(&self) -> bool {
+        !matches!(
+            self,
+            HashAlgorithm::Crc32
+                | HashAlgorithm::Crc32c
+                | HashAlgorithm::Crc16Ccitt
+                | HashAlgorithm::Crc16CcittFalse
+                | HashAlgorithm::Crc16Modbus
+                | HashAlgorithm::Crc32Bzip2
+                | HashAlgorithm::Crc64Ecma
+                | HashAlgorithm::Crc64Xz
+                | HashAlgorithm::Xxh32
+                | HashAlgorithm::Xxh64
+                | HashAlgorithm::Xxh3
+                | HashAlgorithm::Adler32
+                | HashAlgorithm::Fnv1a32
+                | HashAlgorithm::Fnv1a64
+                | HashAlgorithm::Murmur3_32
+                | HashAlgorithm::Murmur3_128
+        )
+    }
 }
 
 /// 哈希操作
@@ -24,4 +106,195 @@ pub trait HasherTrait {
     fn update_bytes(&mut self, vec: &[u8]) -> ();
     /// 获取结果
     fn finalize(&self) -> ByteVector;
+    /// 将结果写入out，out的长度必须为OUTPUT_LENGTH，否则会panic
+    /// 默认实现经由finalize，会分配一次内存
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.finalize());
+    }
+    /// 以定长数组的形式获取结果，N必须等于OUTPUT_LENGTH，否则无法通过编译
+    /// N只能是具体的数值（如SHA256_OUTPUT_LENGTH），在泛型代码中无法写成Self::OUTPUT_LENGTH，
+    /// 泛型代码中应使用finalize_into将结果写入调用者提供的缓冲区
+    fn finalize_array<const N: usize>(&self) -> ByteArray<N> {
+        const { assert!(N == Self::OUTPUT_LENGTH) };
+        let mut res = ByteArray::<N>::new();
+        self.finalize_into(&mut res);
+        res
+    }
+}
+
+/// 可以恢复到刚创建时的状态的哈希操作，从而可以复用同一个哈希操作而不必克隆其上下文
+pub trait ResettableHasherTrait: HasherTrait {
+    /// 恢复到刚创建时的状态，密钥、种子等创建时指定的参数保持不变
+    fn reset(&mut self);
+
+    /// 获取结果并恢复到刚创建时的状态
+    fn finalize_reset(&mut self) -> ByteVector {
+        let res = self.finalize();
+        self.reset();
+        res
+    }
+    /// 以定长数组的形式获取结果并恢复到刚创建时的状态
+    fn finalize_array_reset<const N: usize>(&mut self) -> ByteArray<N> {
+        let res = self.finalize_array::<N>();
+        self.reset();
+        res
+    }
+}
+
+/// 可以在运行时选择的哈希操作
+/// HasherTrait带有关联常量，无法作为trait对象使用，所有实现了HasherTrait的类型都自动实现了DynHasher
+pub trait DynHasher {
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str);
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]);
+    /// 获取结果
+    fn finalize(&self) -> ByteVector;
+    /// 结果的长度
+    fn output_length(&self) -> usize;
+    /// 将结果写入out，out的长度必须为output_length()，否则会panic
+    fn finalize_into(&self, out: &mut [u8]);
+}
+
+impl<HR: HasherTrait> DynHasher for HR {
+    fn update_message(&mut self, message: &str) {
+        HasherTrait::update_message(self, message)
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        HasherTrait::update_bytes(self, vec)
+    }
+
+    fn finalize(&self) -> ByteVector {
+        HasherTrait::finalize(self)
+    }
+
+    fn output_length(&self) -> usize {
+        HR::OUTPUT_LENGTH
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        HasherTrait::finalize_into(self, out)
+    }
+}
+
+/// 可以在运行时选择并且可以重置的哈希操作，所有实现了ResettableHasherTrait的类型都自动实现了DynResettableHasher
+pub trait DynResettableHasher: DynHasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self);
+    /// 获取结果并恢复到刚创建时的状态
+    fn finalize_reset(&mut self) -> ByteVector;
+}
+
+impl<HR: ResettableHasherTrait> DynResettableHasher for HR {
+    fn reset(&mut self) {
+        ResettableHasherTrait::reset(self)
+    }
+
+    fn finalize_reset(&mut self) -> ByteVector {
+        ResettableHasherTrait::finalize_reset(self)
+    }
+}
+
+/// 以固定长度的分组处理消息的哈希算法，HMAC需要知道其分组长度
+pub trait BlockHasherTrait: HasherTrait {
+    const BLOCK_LENGTH: usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashAlgorithm, HasherTrait, ResettableHasherTrait};
+    use crate::hash::adler32::Adler32Hasher;
+    use crate::hash::blake2::{Blake2b512Hasher, Blake2s256Hasher};
+    use crate::hash::blake3::Blake3Hasher;
+    use crate::hash::crc::{Crc16CcittHasher, Crc32Hasher, Crc32cHasher, Crc64XzHasher};
+    use crate::hash::fnv::{Fnv1a32Hasher, Fnv1a64Hasher};
+    use crate::hash::hmac::HmacSha256;
+    use crate::hash::murmur3::{Murmur3_128Hasher, Murmur3_32Hasher};
+    use crate::hash::ripemd160::Ripemd160Hasher;
+    use crate::hash::sha1::Sha1Hasher;
+    use crate::hash::sha224::Sha224Hasher;
+    use crate::hash::sha256::Sha256Hasher;
+    use crate::hash::sha3::{
+        Keccak256Hasher, Sha3_224Hasher, Sha3_256Hasher, Sha3_384Hasher, Sha3_512Hasher,
+        Shake128Hasher, Shake256Hasher,
+    };
+    use crate::hash::sha384::Sha384Hasher;
+    use crate::hash::sha512::Sha512Hasher;
+    use crate::hash::sm3::Sm3Hasher;
+    use crate::hash::xxhash::{Xxh32Hasher, Xxh3Hasher, Xxh64Hasher};
+
+    const MESSAGE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    /// 检查定长输出与finalize一致，并且重置后回到刚创建时的状态（包括密钥和种子）
+    fn check_fixed_output<HR: ResettableHasherTrait, const N: usize>(mut hasher: HR) {
+        let fresh = hasher.finalize();
+        hasher.update_bytes(MESSAGE);
+        let expected = hasher.finalize();
+        let mut out = vec![0x00; HR::OUTPUT_LENGTH];
+        hasher.finalize_into(&mut out);
+        assert_eq!(out, expected);
+        assert_eq!(hasher.finalize_array::<N>().as_ref(), &expected[..]);
+        assert_eq!(hasher.finalize_reset(), expected);
+        assert_eq!(hasher.finalize(), fresh);
+        // 重置后可以继续使用
+        hasher.update_bytes(MESSAGE);
+        assert_eq!(hasher.finalize_array_reset::<N>().as_ref(), &expected[..]);
+        assert_eq!(hasher.finalize(), fresh);
+    }
+
+    #[test]
+    fn test_finalize_array_and_reset() {
+        check_fixed_output::<_, 4>(Crc32Hasher::default());
+        check_fixed_output::<_, 4>(Crc32cHasher::default());
+        check_fixed_output::<_, 2>(Crc16CcittHasher::default());
+        check_fixed_output::<_, 8>(Crc64XzHasher::default());
+        check_fixed_output::<_, 20>(Sha1Hasher::default());
+        check_fixed_output::<_, 28>(Sha224Hasher::default());
+        check_fixed_output::<_, 32>(Sha256Hasher::default());
+        check_fixed_output::<_, 48>(Sha384Hasher::default());
+        check_fixed_output::<_, 64>(Sha512Hasher::default());
+        check_fixed_output::<_, 28>(Sha3_224Hasher::default());
+        check_fixed_output::<_, 32>(Sha3_256Hasher::default());
+        check_fixed_output::<_, 48>(Sha3_384Hasher::default());
+        check_fixed_output::<_, 64>(Sha3_512Hasher::default());
+        check_fixed_output::<_, 32>(Keccak256Hasher::default());
+        check_fixed_output::<_, 32>(Shake128Hasher::<32>::default());
+        check_fixed_output::<_, 100>(Shake256Hasher::<100>::default());
+        check_fixed_output::<_, 64>(Blake2b512Hasher::default());
+        check_fixed_output::<_, 64>(Blake2b512Hasher::new_keyed(b"key").unwrap());
+        check_fixed_output::<_, 32>(Blake2s256Hasher::default());
+        check_fixed_output::<_, 32>(Blake2s256Hasher::new_keyed(b"key").unwrap());
+        check_fixed_output::<_, 32>(Blake3Hasher::default());
+        check_fixed_output::<_, 32>(Blake3Hasher::new_keyed(&[0x42; 32]).unwrap());
+        check_fixed_output::<_, 32>(Blake3Hasher::new_derive_key("ciftl reset test"));
+        check_fixed_output::<_, 32>(Sm3Hasher::default());
+        check_fixed_output::<_, 20>(Ripemd160Hasher::default());
+        check_fixed_output::<_, 4>(Xxh32Hasher::with_seed(1234));
+        check_fixed_output::<_, 8>(Xxh64Hasher::with_seed(1234));
+        check_fixed_output::<_, 8>(Xxh3Hasher::with_seed(1234));
+        check_fixed_output::<_, 4>(Adler32Hasher::with_seed(1234));
+        check_fixed_output::<_, 4>(Fnv1a32Hasher::with_seed(1234));
+        check_fixed_output::<_, 8>(Fnv1a64Hasher::with_seed(1234));
+        check_fixed_output::<_, 4>(Murmur3_32Hasher::with_seed(1234));
+        check_fixed_output::<_, 16>(Murmur3_128Hasher::with_seed(1234));
+        check_fixed_output::<_, 32>(HmacSha256::new(b"key"));
+        #[cfg(feature = "md5")]
+        check_fixed_output::<_, 16>(crate::hash::md5::Md5Hasher::default());
+    }
+
+    #[test]
+    fn test_dyn_hasher_reset() {
+        for algorithm in HashAlgorithm::supported() {
+            let mut hasher = algorithm.new_hasher().unwrap();
+            let fresh = hasher.finalize();
+            hasher.update_bytes(MESSAGE);
+            let expected = hasher.finalize();
+            let mut out = vec![0x00; hasher.output_length()];
+            hasher.finalize_into(&mut out);
+            assert_eq!(out, expected);
+            assert_eq!(hasher.finalize_reset(), expected);
+            assert_eq!(hasher.finalize(), fresh, "{algorithm}");
+        }
+    }
 }
@@ -0,0 +1,156 @@
+//! MurmurHash3哈希操作，底层使用murmur3库
+//! 本模块中的哈希操作都将value()的数值按大端序输出（与XXH、Adler-32一致）
+
+use std::io::Cursor;
+
+use crate::hash::{HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// Murmur3 32位（x86_32）的输出长度
+pub const MURMUR3_32_OUTPUT_LENGTH: usize = 4;
+/// Murmur3 128位（x64_128）的输出长度
+pub const MURMUR3_128_OUTPUT_LENGTH: usize = 16;
+
+/// MurmurHash3 x86_32哈希操作，非密码学哈希
+/// murmur3库只能一次性计算，输入的数据会缓存到计算结果时
+#[derive(Clone, Default)]
+pub struct Murmur3_32Hasher {
+    m_seed: u32,
+    m_buffer: ByteVector,
+}
+
+impl Murmur3_32Hasher {
+    /// 使用种子创建
+    pub fn with_seed(seed: u32) -> Self {
+        Murmur3_32Hasher {
+            m_seed: seed,
+            m_buffer: ByteVector::new(),
+        }
+    }
+
+    /// 获取哈希的数值
+    pub fn value(&self) -> u32 {
+        ::murmur3::murmur3_32(&mut Cursor::new(&self.m_buffer), self.m_seed)
+            .expect("从内存中读取数据不会失败")
+    }
+}
+
+impl HasherTrait for Murmur3_32Hasher {
+    const OUTPUT_LENGTH: usize = MURMUR3_32_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.update_bytes(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_buffer.extend_from_slice(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.value().to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Murmur3_32Hasher {
+    fn reset(&mut self) {
+        self.m_buffer.clear();
+    }
+}
+
+/// MurmurHash3 x64_128哈希操作，非密码学哈希
+/// murmur3库只能一次性计算，输入的数据会缓存到计算结果时
+#[derive(Clone, Default)]
+pub struct Murmur3_128Hasher {
+    m_seed: u32,
+    m_buffer: ByteVector,
+}
+
+impl Murmur3_128Hasher {
+    /// 使用种子创建
+    pub fn with_seed(seed: u32) -> Self {
+        Murmur3_128Hasher {
+            m_seed: seed,
+            m_buffer: ByteVector::new(),
+        }
+    }
+
+    /// 获取哈希的数值，低64位为h1，高64位为h2
+    pub fn value(&self) -> u128 {
+        ::murmur3::murmur3_x64_128(&mut Cursor::new(&self.m_buffer), self.m_seed)
+            .expect("从内存中读取数据不会失败")
+    }
+}
+
+impl HasherTrait for Murmur3_128Hasher {
+    const OUTPUT_LENGTH: usize = MURMUR3_128_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.update_bytes(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_buffer.extend_from_slice(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.value().to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Murmur3_128Hasher {
+    fn reset(&mut self) {
+        self.m_buffer.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur3_32() {
+        let cases = [
+            ("", 0, 0x00000000),
+            ("", 1, 0x514E28B7),
+            ("", 0xFFFFFFFF, 0x81F16F39),
+            ("test", 0, 0xBA6BD213),
+            ("Hello, world!", 1234, 0xFAF6CDB3),
+            (
+                "The quick brown fox jumps over the lazy dog",
+                0x9747B28C,
+                0x2FA826CD,
+            ),
+        ];
+        for (message, seed, expected) in cases {
+            let mut hasher = Murmur3_32Hasher::with_seed(seed);
+            hasher.update_message(message);
+            assert_eq!(hasher.value(), expected);
+            assert_eq!(hasher.finalize(), expected.to_be_bytes().to_vec());
+        }
+    }
+
+    #[test]
+    fn test_murmur3_128() {
+        let mut hasher = Murmur3_128Hasher::default();
+        assert_eq!(hasher.finalize(), vec![0x00; MURMUR3_128_OUTPUT_LENGTH]);
+        hasher.update_message("The quick brown fox jumps over the lazy dog");
+        assert_eq!(hasher.value(), 0x7A433CA9C49A9347E34BBC7BBC071B6C);
+        assert_eq!(hasher.finalize()[0], 0x7A);
+        // 分段输入与一次性输入的结果相同
+        let mut chunked = Murmur3_128Hasher::default();
+        for chunk in b"The quick brown fox jumps over the lazy dog".chunks(7) {
+            chunked.update_bytes(chunk);
+        }
+        assert_eq!(chunked.value(), hasher.value());
+        chunked.reset();
+        assert_eq!(chunked.value(), 0);
+    }
+}
@@ -0,0 +1,70 @@
+use ::ripemd::{Digest, Ripemd160 as ExRipemd160};
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// Ripemd160的输出长度
+pub const RIPEMD160_OUTPUT_LENGTH: usize = 20;
+/// Ripemd160的分组长度
+pub const RIPEMD160_BLOCK_LENGTH: usize = 64;
+
+/// Ripemd160哈希操作
+#[derive(Clone, Default)]
+pub struct Ripemd160Hasher {
+    m_ctx: ExRipemd160,
+}
+
+impl HasherTrait for Ripemd160Hasher {
+    const OUTPUT_LENGTH: usize = RIPEMD160_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.clone().finalize().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_ctx.clone().finalize());
+    }
+}
+
+impl ResettableHasherTrait for Ripemd160Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        Digest::reset(&mut self.m_ctx);
+    }
+}
+
+impl BlockHasherTrait for Ripemd160Hasher {
+    const BLOCK_LENGTH: usize = RIPEMD160_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_ripemd160_operation() {
+        let hexe = HexEncoding::default();
+        let mut ripemd160_hasher = Ripemd160Hasher::default();
+        assert_eq!(
+            "9C1185A5C5E9FC54612808977EE8F548B2258D31".to_string(),
+            hexe.encode(&ripemd160_hasher.finalize())
+        );
+        // abc
+        ripemd160_hasher.update_message("abc");
+        assert_eq!(
+            "8EB208F7E05D987A9B044A8E98C6B087F15A0BFC".to_string(),
+            hexe.encode(&ripemd160_hasher.finalize())
+        );
+    }
+}
@@ -2,9 +2,11 @@ use crate::*;
 use ring::digest;
 use ring::digest::SHA1_OUTPUT_LEN;
 
-use crate::hash::HasherTrait;
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
 
 pub const SHA1_OUTPUT_LENGTH: usize = SHA1_OUTPUT_LEN;
+/// Sha1的分组长度
+pub const SHA1_BLOCK_LENGTH: usize = 64;
 
 /// Sha1哈希操作
 pub struct Sha1Hasher {
@@ -41,6 +43,21 @@ impl HasherTrait for Sha1Hasher {
         }
         res
     }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_ctx.clone().finish().as_ref());
+    }
+}
+
+impl ResettableHasherTrait for Sha1Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_ctx = digest::Context::new(self.m_ctx.algorithm());
+    }
+}
+
+impl BlockHasherTrait for Sha1Hasher {
+    const BLOCK_LENGTH: usize = SHA1_BLOCK_LENGTH;
 }
 
 #[cfg(test)]
@@ -0,0 +1,73 @@
+use ::sha2::{Digest, Sha224 as ExSha224};
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// Sha224的输出长度
+pub const SHA224_OUTPUT_LENGTH: usize = 28;
+/// Sha224的分组长度
+pub const SHA224_BLOCK_LENGTH: usize = 64;
+
+/// Sha224哈希操作，ring不支持Sha224，因此使用sha2实现
+#[derive(Clone, Default)]
+pub struct Sha224Hasher {
+    m_ctx: ExSha224,
+}
+
+impl HasherTrait for Sha224Hasher {
+    const OUTPUT_LENGTH: usize = SHA224_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.clone().finalize().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_ctx.clone().finalize());
+    }
+}
+
+impl ResettableHasherTrait for Sha224Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        Digest::reset(&mut self.m_ctx);
+    }
+}
+
+impl BlockHasherTrait for Sha224Hasher {
+    const BLOCK_LENGTH: usize = SHA224_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_sha224_operation() {
+        let hexe = HexEncoding::default();
+        let mut sha224_hasher = Sha224Hasher::default();
+        // 123456
+        sha224_hasher.update_message("123456");
+        assert_eq!(
+            "F8CDB04495DED47615258F9DC6A3F4707FD2405434FEFC3CBF4EF4E6".to_string(),
+            hexe.encode(&sha224_hasher.finalize())
+        );
+        // abc
+        let mut sha224_hasher = Sha224Hasher::default();
+        sha224_hasher.update_message("abc");
+        assert_eq!(
+            "23097D223405D8228642A477BDA255B32AADBCE4BDA0B3F7E36C9DA7".to_string(),
+            hexe.encode(&sha224_hasher.finalize())
+        );
+    }
+}
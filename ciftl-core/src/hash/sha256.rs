@@ -4,9 +4,11 @@ use crate::*;
 use ring::digest;
 use ring::digest::SHA256_OUTPUT_LEN;
 
-use crate::hash::HasherTrait;
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
 
 pub const SHA256_OUTPUT_LENGTH: usize = SHA256_OUTPUT_LEN;
+/// Sha256的分组长度
+pub const SHA256_BLOCK_LENGTH: usize = 64;
 
 /// Sha1哈希操作
 pub struct Sha256Hasher {
@@ -43,6 +45,21 @@ impl HasherTrait for Sha256Hasher {
         }
         res
     }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_ctx.clone().finish().as_ref());
+    }
+}
+
+impl ResettableHasherTrait for Sha256Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_ctx = digest::Context::new(self.m_ctx.algorithm());
+    }
+}
+
+impl BlockHasherTrait for Sha256Hasher {
+    const BLOCK_LENGTH: usize = SHA256_BLOCK_LENGTH;
 }
 
 #[cfg(test)]
@@ -0,0 +1,209 @@
+use ::sha3::digest::core_api::BlockSizeUser;
+use ::sha3::digest::typenum::Unsigned;
+use ::sha3::digest::{Digest, ExtendableOutput, OutputSizeUser, Update, XofReader};
+use ::sha3::{
+    Keccak256 as ExKeccak256, Sha3_224 as ExSha3_224, Sha3_256 as ExSha3_256,
+    Sha3_384 as ExSha3_384, Sha3_512 as ExSha3_512, Shake128 as ExShake128, Shake256 as ExShake256,
+};
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// SHAKE128默认的输出长度，对应128位的安全强度
+pub const SHAKE128_DEFAULT_OUTPUT_LENGTH: usize = 32;
+/// SHAKE256默认的输出长度，对应256位的安全强度
+pub const SHAKE256_DEFAULT_OUTPUT_LENGTH: usize = 64;
+
+/// Sha3系列（包括Keccak）的固定输出长度的哈希操作
+#[derive(Clone, Default)]
+pub struct Sha3Hasher<D: Digest + BlockSizeUser + Clone + Default> {
+    m_ctx: D,
+}
+
+/// Sha3-224
+pub type Sha3_224Hasher = Sha3Hasher<ExSha3_224>;
+/// Sha3-256
+pub type Sha3_256Hasher = Sha3Hasher<ExSha3_256>;
+/// Sha3-384
+pub type Sha3_384Hasher = Sha3Hasher<ExSha3_384>;
+/// Sha3-512
+pub type Sha3_512Hasher = Sha3Hasher<ExSha3_512>;
+/// Keccak-256，即以太坊使用的填充方式不同于Sha3-256的版本
+pub type Keccak256Hasher = Sha3Hasher<ExKeccak256>;
+
+impl<D: Digest + BlockSizeUser + Clone + Default> HasherTrait for Sha3Hasher<D> {
+    const OUTPUT_LENGTH: usize = <D as OutputSizeUser>::OutputSize::USIZE;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        Digest::update(&mut self.m_ctx, message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        Digest::update(&mut self.m_ctx, vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.clone().finalize().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_ctx.clone().finalize());
+    }
+}
+
+impl<D: Digest + BlockSizeUser + Clone + Default> ResettableHasherTrait for Sha3Hasher<D> {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_ctx = D::default();
+    }
+}
+
+impl<D: Digest + BlockSizeUser + Clone + Default> BlockHasherTrait for Sha3Hasher<D> {
+    /// Sha3的分组长度即海绵结构的吸收速率
+    const BLOCK_LENGTH: usize = <D as BlockSizeUser>::BlockSize::USIZE;
+}
+
+/// SHAKE系列的可变输出长度的哈希操作，N为作为HasherTrait使用时的输出长度
+#[derive(Clone, Default)]
+pub struct ShakeHasher<S: ExtendableOutput + Update + Clone + Default, const N: usize> {
+    m_ctx: S,
+}
+
+/// SHAKE128
+pub type Shake128Hasher<const N: usize = SHAKE128_DEFAULT_OUTPUT_LENGTH> =
+    ShakeHasher<ExShake128, N>;
+/// SHAKE256
+pub type Shake256Hasher<const N: usize = SHAKE256_DEFAULT_OUTPUT_LENGTH> =
+    ShakeHasher<ExShake256, N>;
+
+impl<S: ExtendableOutput + Update + Clone + Default, const N: usize> ShakeHasher<S, N> {
+    /// 获取任意长度的结果
+    pub fn finalize_xof(&self, n: usize) -> ByteVector {
+        let mut res = vec![0x00; n];
+        self.m_ctx.clone().finalize_xof().read(&mut res);
+        res
+    }
+}
+
+impl<S: ExtendableOutput + Update + Clone + Default, const N: usize> HasherTrait
+    for ShakeHasher<S, N>
+{
+    const OUTPUT_LENGTH: usize = N;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.finalize_xof(N)
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        assert_eq!(out.len(), N);
+        self.m_ctx.clone().finalize_xof().read(out);
+    }
+}
+
+impl<S: ExtendableOutput + Update + Clone + Default, const N: usize> ResettableHasherTrait
+    for ShakeHasher<S, N>
+{
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_ctx = S::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    fn hash_hex<HR: HasherTrait + Default>(message: &str) -> String {
+        let mut hasher = HR::default();
+        hasher.update_message(message);
+        let res = hasher.finalize();
+        assert_eq!(res.len(), HR::OUTPUT_LENGTH);
+        HexEncoding::default().encode(&res)
+    }
+
+    #[test]
+    fn test_sha3() {
+        // FIPS 202 示例中的消息"abc"和空消息
+        assert_eq!(
+            hash_hex::<Sha3_224Hasher>("abc"),
+            "E642824C3F8CF24AD09234EE7D3C766FC9A3A5168D0C94AD73B46FDF"
+        );
+        assert_eq!(
+            hash_hex::<Sha3_224Hasher>(""),
+            "6B4E03423667DBB73B6E15454F0EB1ABD4597F9A1B078E3F5B5A6BC7"
+        );
+        assert_eq!(
+            hash_hex::<Sha3_256Hasher>("abc"),
+            "3A985DA74FE225B2045C172D6BD390BD855F086E3E9D525B46BFE24511431532"
+        );
+        assert_eq!(
+            hash_hex::<Sha3_256Hasher>(""),
+            "A7FFC6F8BF1ED76651C14756A061D662F580FF4DE43B49FA82D80A4B80F8434A"
+        );
+        assert_eq!(
+            hash_hex::<Sha3_384Hasher>("abc"),
+            "EC01498288516FC926459F58E2C6AD8DF9B473CB0FC08C2596DA7CF0E49BE4B298D88CEA927AC7F539F1EDF228376D25"
+        );
+        assert_eq!(
+            hash_hex::<Sha3_512Hasher>("abc"),
+            "B751850B1A57168A5693CD924B6B096E08F621827444F70D884F5D0240D2712E10E116E9192AF3C91A7EC57647E3934057340B4CF408D5A56592F8274EEC53F0"
+        );
+        // 448位的消息，分两次输入
+        let mut hasher = Sha3_256Hasher::default();
+        hasher.update_message("abcdbcdecdefdefgefghfghighijhijk");
+        hasher.update_message("ijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize()),
+            "41C0DBA2A9D6240849100376A8235E2C82E1B9998A999E21DB32DD97496D3376"
+        );
+        assert_eq!(Sha3_256Hasher::BLOCK_LENGTH, 136);
+        assert_eq!(Sha3_512Hasher::BLOCK_LENGTH, 72);
+    }
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            hash_hex::<Keccak256Hasher>(""),
+            "C5D2460186F7233C927E7DB2DCC703C0E500B653CA82273B7BFAD8045D85A470"
+        );
+        assert_eq!(
+            hash_hex::<Keccak256Hasher>("abc"),
+            "4E03657AEA45A94FC7D47BA826C8D667C0D1E6E33A64A036EC44F58FA12D6C45"
+        );
+    }
+
+    #[test]
+    fn test_shake() {
+        assert_eq!(
+            hash_hex::<Shake128Hasher>(""),
+            "7F9C2BA4E88F827D616045507605853ED73B8093F6EFBC88EB1A6EACFA66EF26"
+        );
+        assert_eq!(
+            hash_hex::<Shake256Hasher>("abc"),
+            "483366601360A8771C6863080CC4114D8DB44530F8F1E1EE4F94EA37E78B5739D5A15BEF186A5386C75744C0527E1FAA9F8726E462A12A4FEB06BD8801E751E4"
+        );
+        // 指定输出长度
+        assert_eq!(
+            hash_hex::<Shake128Hasher<16>>("abc"),
+            "5881092DD818BF5CF8A3DDB793FBCBA7"
+        );
+        let mut hasher = Shake128Hasher::<16>::default();
+        hasher.update_message("abc");
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize_xof(100)),
+            "5881092DD818BF5CF8A3DDB793FBCBA74097D5C526A6D35F97B83351940F2CC844C50AF32ACD3F2CDD066568706F509BC1BDDE58295DAE3F891A9A0FCA5783789A41F8611214CE612394DF286A62D1A2252AA94DB9C538956C717DC2BED4F232A0294C85"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use ring::digest;
+use ring::digest::SHA384_OUTPUT_LEN;
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+pub const SHA384_OUTPUT_LENGTH: usize = SHA384_OUTPUT_LEN;
+/// Sha384的分组长度
+pub const SHA384_BLOCK_LENGTH: usize = 128;
+
+/// Sha384哈希操作
+pub struct Sha384Hasher {
+    m_ctx: digest::Context,
+}
+
+impl Default for Sha384Hasher {
+    fn default() -> Sha384Hasher {
+        Sha384Hasher {
+            m_ctx: digest::Context::new(&digest::SHA384),
+        }
+    }
+}
+
+impl HasherTrait for Sha384Hasher {
+    const OUTPUT_LENGTH: usize = SHA384_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.clone().finish().as_ref().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_ctx.clone().finish().as_ref());
+    }
+}
+
+impl ResettableHasherTrait for Sha384Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_ctx = digest::Context::new(self.m_ctx.algorithm());
+    }
+}
+
+impl BlockHasherTrait for Sha384Hasher {
+    const BLOCK_LENGTH: usize = SHA384_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+
+    #[test]
+    fn test_sha384_operation() {
+        let hexe = HexEncoding::default();
+        let mut sha384_hasher = Sha384Hasher::default();
+        // 123456
+        sha384_hasher.update_message("123456");
+        assert_eq!(
+            "0A989EBC4A77B56A6E2BB7B19D995D185CE44090C13E2984B7ECC6D446D4B61EA9991B76A4C2F04B1B4D244841449454".to_string(),
+            hexe.encode(&sha384_hasher.finalize())
+        );
+        // abc
+        let mut sha384_hasher = Sha384Hasher::default();
+        sha384_hasher.update_message("abc");
+        assert_eq!(
+            "CB00753F45A35E8BB5A03D699AC65007272C32AB0EDED1631A8B605A43FF5BED8086072BA1E7CC2358BAECA134C825A7".to_string(),
+            hexe.encode(&sha384_hasher.finalize())
+        );
+    }
+}
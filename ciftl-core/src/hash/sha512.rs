@@ -2,9 +2,11 @@ use crate::*;
 use ring::digest;
 use ring::digest::SHA512_OUTPUT_LEN;
 
-use crate::hash::HasherTrait;
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
 
 pub const SHA512_OUTPUT_LENGTH: usize = SHA512_OUTPUT_LEN;
+/// Sha512的分组长度
+pub const SHA512_BLOCK_LENGTH: usize = 128;
 
 /// Sha1哈希操作
 pub struct Sha512Hasher {
@@ -41,6 +43,21 @@ impl HasherTrait for Sha512Hasher {
         }
         res
     }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.m_ctx.clone().finish().as_ref());
+    }
+}
+
+impl ResettableHasherTrait for Sha512Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        self.m_ctx = digest::Context::new(self.m_ctx.algorithm());
+    }
+}
+
+impl BlockHasherTrait for Sha512Hasher {
+    const BLOCK_LENGTH: usize = SHA512_BLOCK_LENGTH;
 }
 
 #[cfg(test)]
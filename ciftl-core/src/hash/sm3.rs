@@ -0,0 +1,95 @@
+use ::sm3::{Digest, Sm3 as ExSm3};
+
+use crate::hash::{BlockHasherTrait, HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// SM3的输出长度
+pub const SM3_OUTPUT_LENGTH: usize = 32;
+/// SM3的分组长度
+pub const SM3_BLOCK_LENGTH: usize = 64;
+
+/// SM3哈希操作（GB/T 32905-2016）
+#[derive(Clone, Default)]
+pub struct Sm3Hasher {
+    m_ctx: ExSm3,
+}
+
+impl HasherTrait for Sm3Hasher {
+    const OUTPUT_LENGTH: usize = SM3_OUTPUT_LENGTH;
+
+    /// 计算一个消息的哈希值
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+    /// 计算一个字节数组的哈希值
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+    /// 获取结果
+    fn finalize(&self) -> ByteVector {
+        self.m_ctx.clone().finalize().to_vec()
+    }
+    /// 将结果写入out
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.m_ctx.clone().finalize());
+    }
+}
+
+impl ResettableHasherTrait for Sm3Hasher {
+    /// 恢复到刚创建时的状态
+    fn reset(&mut self) {
+        Digest::reset(&mut self.m_ctx);
+    }
+}
+
+impl BlockHasherTrait for Sm3Hasher {
+    const BLOCK_LENGTH: usize = SM3_BLOCK_LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use crate::hash::hmac::Hmac;
+
+    fn sm3_hex(message: &str) -> String {
+        let mut hasher = Sm3Hasher::default();
+        hasher.update_message(message);
+        HexEncoding::default().encode(&hasher.finalize())
+    }
+
+    #[test]
+    fn test_sm3() {
+        // GB/T 32905-2016 附录A中的两个示例
+        assert_eq!(
+            sm3_hex("abc"),
+            "66C7F0F462EEEDD9D1F2D46BDC10E4E24167C4875CF2F7A2297DA02B8F4BA8E0"
+        );
+        assert_eq!(
+            sm3_hex(&"abcd".repeat(16)),
+            "DEBE9FF92275B8A138604889C18E5A4D6FDB70E5387E5765293DCBA39C0C5732"
+        );
+        assert_eq!(
+            sm3_hex(""),
+            "1AB21D8355CFA17F8E61194831E81A8F22BEC8C728FEFB747ED035EB5082AA2B"
+        );
+        // 以不规则的长度分段输入，结果与一次输入相同
+        let message = "abcd".repeat(40);
+        let mut hasher = Sm3Hasher::default();
+        for chunk in message.as_bytes().chunks(7) {
+            hasher.update_bytes(chunk);
+        }
+        assert_eq!(
+            HexEncoding::default().encode(&hasher.finalize()),
+            sm3_hex(&message)
+        );
+        // 可以作为HMAC的底层哈希
+        let mut hmac = Hmac::<Sm3Hasher>::new(b"key");
+        hmac.update_message("abc");
+        assert_eq!(
+            HexEncoding::default().encode(&hmac.finalize()),
+            "28E63256E7C5A087B1F073265DC53092163F7B82729735D06F28F10AF9D52393"
+        );
+    }
+}
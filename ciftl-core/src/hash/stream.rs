@@ -0,0 +1,368 @@
+use std::fs::File;
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::Path;
+
+use ::sha3::digest::core_api::BlockSizeUser;
+use ::sha3::digest::{Digest, ExtendableOutput, Update};
+
+use crate::hash::adler32::Adler32Hasher;
+use crate::hash::blake2::{Blake2b512Hasher, Blake2s256Hasher};
+use crate::hash::blake3::Blake3Hasher;
+use crate::hash::crc::{Crc32Hasher, Crc32cHasher, CrcHasher, CrcPresetTrait};
+use crate::hash::fnv::{Fnv1a32Hasher, Fnv1a64Hasher};
+use crate::hash::hmac::Hmac;
+use crate::hash::murmur3::{Murmur3_128Hasher, Murmur3_32Hasher};
+use crate::hash::ripemd160::Ripemd160Hasher;
+use crate::hash::sha1::Sha1Hasher;
+use crate::hash::sha224::Sha224Hasher;
+use crate::hash::sha256::Sha256Hasher;
+use crate::hash::sha3::{Sha3Hasher, ShakeHasher};
+use crate::hash::sha384::Sha384Hasher;
+use crate::hash::sha512::Sha512Hasher;
+use crate::hash::sm3::Sm3Hasher;
+use crate::hash::xxhash::{Xxh32Hasher, Xxh3Hasher, Xxh64Hasher};
+use crate::hash::{BlockHasherTrait, DynHasher, DynResettableHasher, HashAlgorithm, HasherTrait};
+use crate::*;
+
+/// 分块读取数据时缓冲区的长度
+pub const HASH_READ_BUFFER_LENGTH: usize = 64 * 1024;
+/// 不小于该长度的文件通过内存映射读取
+#[cfg(feature = "mmap")]
+pub const HASH_MMAP_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+/// 分块读取reader中剩余的全部数据并输入哈希操作，返回读取的总长度
+fn read_into_hasher<H: DynHasher + ?Sized, R: Read + ?Sized>(
+    hasher: &mut H,
+    reader: &mut R,
+) -> io::Result<u64> {
+    let mut buf = vec![0x00; HASH_READ_BUFFER_LENGTH];
+    let mut total_length = 0u64;
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                hasher.update_bytes(&buf[..n]);
+                total_length += n as u64;
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total_length)
+}
+
+/// 将reader中剩余的全部数据分块输入哈希操作，返回读取的总长度
+/// 内存占用只有一个缓冲区，与数据的长度无关
+pub fn update_reader<H: DynHasher + ?Sized, R: Read + ?Sized>(
+    hasher: &mut H,
+    reader: &mut R,
+) -> Result<u64> {
+    read_into_hasher(hasher, reader)
+        .map_err(|e| FAILED_WHEN_READING_HASH_INPUT.add_opt_mess(&format!("{e}")))
+}
+
+/// 将一个文件的全部内容输入哈希操作，不小于mmap_threshold的文件通过内存映射读取
+#[cfg_attr(not(feature = "mmap"), allow(unused_variables))]
+fn update_file_impl<H: DynHasher + ?Sized>(
+    hasher: &mut H,
+    path: &Path,
+    mmap_threshold: u64,
+) -> Result<u64> {
+    let read_file = |hasher: &mut H| -> io::Result<u64> {
+        let mut file = File::open(path)?;
+        #[cfg(feature = "mmap")]
+        if file.metadata()?.len() >= mmap_threshold {
+            // SAFETY: 映射期间文件被其它进程修改时读取到的内容不确定
+            // 文件被截断时访问超出文件结尾的页会收到SIGBUS而使整个进程崩溃
+            // 调用者需要保证哈希期间文件不被修改或截断，无法保证时应关闭mmap特性或使用更大的阈值
+            let mmap = unsafe { ::memmap2::Mmap::map(&file)? };
+            hasher.update_bytes(&mmap);
+            return Ok(mmap.len() as u64);
+        }
+        read_into_hasher(hasher, &mut file)
+    };
+    read_file(hasher).map_err(|e| {
+        FAILED_WHEN_READING_HASH_INPUT.add_opt_mess(&format!("{}: {e}", path.display()))
+    })
+}
+
+/// 将一个文件的全部内容输入哈希操作，返回文件的长度
+/// 启用mmap特性时，不小于HASH_MMAP_THRESHOLD的文件通过内存映射读取，否则分块读取
+pub fn update_file<H: DynHasher + ?Sized, P: AsRef<Path>>(hasher: &mut H, path: P) -> Result<u64> {
+    #[cfg(feature = "mmap")]
+    let mmap_threshold = HASH_MMAP_THRESHOLD;
+    #[cfg(not(feature = "mmap"))]
+    let mmap_threshold = u64::MAX;
+    update_file_impl(hasher, path.as_ref(), mmap_threshold)
+}
+
+/// 与update_file相同，但不小于mmap_threshold的文件才通过内存映射读取
+/// mmap_threshold为u64::MAX时不使用内存映射，注意内存映射的文件被截断时进程会崩溃
+#[cfg(feature = "mmap")]
+pub fn update_file_with_mmap_threshold<H: DynHasher + ?Sized, P: AsRef<Path>>(
+    hasher: &mut H,
+    path: P,
+    mmap_threshold: u64,
+) -> Result<u64> {
+    update_file_impl(hasher, path.as_ref(), mmap_threshold)
+}
+
+/// 计算reader中剩余的全部数据的哈希值
+pub fn hash_reader<HR: HasherTrait + Default, R: Read + ?Sized>(
+    reader: &mut R,
+) -> Result<ByteVector> {
+    let mut hasher = HR::default();
+    update_reader(&mut hasher, reader)?;
+    Ok(HasherTrait::finalize(&hasher))
+}
+
+/// 计算一个文件的哈希值
+pub fn hash_file<HR: HasherTrait + Default, P: AsRef<Path>>(path: P) -> Result<ByteVector> {
+    let mut hasher = HR::default();
+    update_file(&mut hasher, path)?;
+    Ok(HasherTrait::finalize(&hasher))
+}
+
+impl HashAlgorithm {
+    /// 使用该算法计算reader中剩余的全部数据的哈希值
+    pub fn hash_reader<R: Read + ?Sized>(&self, reader: &mut R) -> Result<ByteVector> {
+        let mut hasher = self.new_hasher()?;
+        update_reader(hasher.as_mut(), reader)?;
+        Ok(hasher.finalize())
+    }
+
+    /// 使用该算法计算一个文件的哈希值
+    pub fn hash_file<P: AsRef<Path>>(&self, path: P) -> Result<ByteVector> {
+        let mut hasher = self.new_hasher()?;
+        update_file(hasher.as_mut(), path)?;
+        Ok(hasher.finalize())
+    }
+}
+
+/// 以io::Write的形式向哈希操作输入数据，从而可以使用io::copy等函数
+impl Write for dyn DynHasher + '_ {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Write for dyn DynResettableHasher + '_ {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 为具体的哈希操作实现io::Write，从而可以直接io::copy到哈希操作中
+/// 孤儿规则不允许为所有实现了HasherTrait的类型统一实现io::Write，因此逐个实现
+macro_rules! impl_write_for_hasher {
+    ($([$($generics:tt)*] $hasher:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> Write for $hasher {
+                fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                    HasherTrait::update_bytes(self, buf);
+                    Ok(buf.len())
+                }
+
+                fn flush(&mut self) -> io::Result<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_write_for_hasher!(
+    [] Crc32Hasher,
+    [] Crc32cHasher,
+    [P: CrcPresetTrait] CrcHasher<P>,
+    [] Sha1Hasher,
+    [] Sha224Hasher,
+    [] Sha256Hasher,
+    [] Sha384Hasher,
+    [] Sha512Hasher,
+    [D: Digest + BlockSizeUser + Clone + Default] Sha3Hasher<D>,
+    [S: ExtendableOutput + Update + Clone + Default, const N: usize] ShakeHasher<S, N>,
+    [] Blake2b512Hasher,
+    [] Blake2s256Hasher,
+    [] Blake3Hasher,
+    [] Sm3Hasher,
+    [] Ripemd160Hasher,
+    [] Xxh32Hasher,
+    [] Xxh64Hasher,
+    [] Xxh3Hasher,
+    [] Adler32Hasher,
+    [] Fnv1a32Hasher,
+    [] Fnv1a64Hasher,
+    [] Murmur3_32Hasher,
+    [] Murmur3_128Hasher,
+    [HR: BlockHasherTrait + Default] Hmac<HR>,
+);
+#[cfg(feature = "md5")]
+impl_write_for_hasher!([] crate::hash::md5::Md5Hasher);
+
+/// 将一个哈希操作包装为io::Write
+/// 具体的哈希操作本身已经实现了io::Write，HashWriter用于泛型代码中只知道HR: HasherTrait的情况
+#[derive(Clone, Default)]
+pub struct HashWriter<HR: HasherTrait> {
+    m_hasher: HR,
+}
+
+impl<HR: HasherTrait> HashWriter<HR> {
+    /// 包装一个哈希操作
+    pub fn new(hasher: HR) -> Self {
+        HashWriter { m_hasher: hasher }
+    }
+
+    /// 获取结果
+    pub fn finalize(&self) -> ByteVector {
+        self.m_hasher.finalize()
+    }
+
+    /// 取出内部的哈希操作
+    pub fn into_inner(self) -> HR {
+        self.m_hasher
+    }
+}
+
+impl<HR: HasherTrait> Write for HashWriter<HR> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.m_hasher.update_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex::HexEncoding;
+    use crate::encoding::EncodingTrait;
+    use crate::hash::sha256::Sha256Hasher;
+    use crate::hash::sm3::Sm3Hasher;
+    use std::io::Cursor;
+
+    fn test_data(len: usize) -> ByteVector {
+        (0..len).map(|i| (i * 7 + i / 256) as u8).collect()
+    }
+
+    fn sha256(data: &[u8]) -> ByteVector {
+        let mut hasher = Sha256Hasher::default();
+        HasherTrait::update_bytes(&mut hasher, data);
+        HasherTrait::finalize(&hasher)
+    }
+
+    #[test]
+    fn test_hash_reader() {
+        // 长度分别为空、小于一个缓冲区、恰好若干个缓冲区以及跨越缓冲区边界
+        for len in [
+            0,
+            100,
+            HASH_READ_BUFFER_LENGTH,
+            3 * HASH_READ_BUFFER_LENGTH,
+            3 * HASH_READ_BUFFER_LENGTH + 17,
+        ] {
+            let data = test_data(len);
+            let res = hash_reader::<Sha256Hasher, _>(&mut Cursor::new(&data)).unwrap();
+            assert_eq!(res, sha256(&data));
+            let res = HashAlgorithm::Sha256
+                .hash_reader(&mut Cursor::new(&data))
+                .unwrap();
+            assert_eq!(res, sha256(&data));
+        }
+        assert_eq!(
+            HexEncoding::default()
+                .encode(&hash_reader::<Sm3Hasher, _>(&mut Cursor::new(b"abc")).unwrap()),
+            "66C7F0F462EEEDD9D1F2D46BDC10E4E24167C4875CF2F7A2297DA02B8F4BA8E0"
+        );
+    }
+
+    #[test]
+    fn test_hash_writer() {
+        let data = test_data(2 * HASH_READ_BUFFER_LENGTH + 5);
+        // 包装具体的哈希操作
+        let mut writer = HashWriter::<Sha256Hasher>::default();
+        let n = io::copy(&mut Cursor::new(&data), &mut writer).unwrap();
+        assert_eq!(n, data.len() as u64);
+        assert_eq!(writer.finalize(), sha256(&data));
+        // 具体的哈希操作本身
+        let mut hasher = Sha256Hasher::default();
+        io::copy(&mut Cursor::new(&data), &mut hasher).unwrap();
+        assert_eq!(HasherTrait::finalize(&hasher), sha256(&data));
+        let mut hasher = Sm3Hasher::default();
+        hasher.write_all(b"abc").unwrap();
+        assert_eq!(
+            HexEncoding::default().encode(&HasherTrait::finalize(&hasher)),
+            "66C7F0F462EEEDD9D1F2D46BDC10E4E24167C4875CF2F7A2297DA02B8F4BA8E0"
+        );
+        // 运行时选择的哈希操作
+        let mut hasher = HashAlgorithm::Sha256.new_hasher().unwrap();
+        io::copy(&mut Cursor::new(&data), hasher.as_mut()).unwrap();
+        assert_eq!(hasher.finalize(), sha256(&data));
+    }
+
+    #[test]
+    fn test_hash_file() {
+        let data = test_data(HASH_READ_BUFFER_LENGTH + 1000);
+        let path =
+            std::env::temp_dir().join(format!("ciftl_hash_stream_test_{}.bin", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+        assert_eq!(hash_file::<Sha256Hasher, _>(&path).unwrap(), sha256(&data));
+        assert_eq!(
+            HashAlgorithm::Sha256.hash_file(&path).unwrap(),
+            sha256(&data)
+        );
+        let mut hasher = Sha256Hasher::default();
+        assert_eq!(update_file(&mut hasher, &path).unwrap(), data.len() as u64);
+        std::fs::remove_file(&path).unwrap();
+        // 文件不存在
+        let res = hash_file::<Sha256Hasher, _>(&path);
+        assert!(res.is_err());
+        println!("错误：{}", res.err().unwrap());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_hash_file_mmap() {
+        let data = test_data(HASH_MMAP_THRESHOLD as usize + 3);
+        let path = std::env::temp_dir().join(format!(
+            "ciftl_hash_stream_mmap_test_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, &data).unwrap();
+        let res = hash_file::<Sha256Hasher, _>(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(res.unwrap(), sha256(&data));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_update_file_with_mmap_threshold() {
+        let data = test_data(HASH_READ_BUFFER_LENGTH + 7);
+        let path = std::env::temp_dir().join(format!(
+            "ciftl_hash_stream_mmap_threshold_test_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, &data).unwrap();
+        // 分别通过内存映射和分块读取
+        for threshold in [1, u64::MAX] {
+            let mut hasher = Sha256Hasher::default();
+            let n = update_file_with_mmap_threshold(&mut hasher, &path, threshold).unwrap();
+            assert_eq!(n, data.len() as u64);
+            assert_eq!(HasherTrait::finalize(&hasher), sha256(&data));
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
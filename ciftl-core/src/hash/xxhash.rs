@@ -0,0 +1,225 @@
+use ::xxhash_rust::xxh3::Xxh3 as ExXxh3;
+use ::xxhash_rust::xxh32::Xxh32 as ExXxh32;
+use ::xxhash_rust::xxh64::Xxh64 as ExXxh64;
+
+use crate::hash::{HasherTrait, ResettableHasherTrait};
+use crate::*;
+
+/// XXH32的输出长度
+pub const XXH32_OUTPUT_LENGTH: usize = 4;
+/// XXH64的输出长度
+pub const XXH64_OUTPUT_LENGTH: usize = 8;
+/// XXH3（64位）的输出长度
+pub const XXH3_OUTPUT_LENGTH: usize = 8;
+
+/// XXH32哈希操作，非密码学哈希，结果按大端序输出（与xxhsum一致）
+#[derive(Clone)]
+pub struct Xxh32Hasher {
+    m_ctx: ExXxh32,
+    m_seed: u32,
+}
+
+impl Xxh32Hasher {
+    /// 使用种子创建
+    pub fn with_seed(seed: u32) -> Self {
+        Xxh32Hasher {
+            m_ctx: ExXxh32::new(seed),
+            m_seed: seed,
+        }
+    }
+
+    /// 获取哈希的数值
+    pub fn value(&self) -> u32 {
+        self.m_ctx.digest()
+    }
+}
+
+impl Default for Xxh32Hasher {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl HasherTrait for Xxh32Hasher {
+    const OUTPUT_LENGTH: usize = XXH32_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.value().to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Xxh32Hasher {
+    fn reset(&mut self) {
+        self.m_ctx.reset(self.m_seed);
+    }
+}
+
+/// XXH64哈希操作，非密码学哈希，结果按大端序输出（与xxhsum一致）
+#[derive(Clone)]
+pub struct Xxh64Hasher {
+    m_ctx: ExXxh64,
+    m_seed: u64,
+}
+
+impl Xxh64Hasher {
+    /// 使用种子创建
+    pub fn with_seed(seed: u64) -> Self {
+        Xxh64Hasher {
+            m_ctx: ExXxh64::new(seed),
+            m_seed: seed,
+        }
+    }
+
+    /// 获取哈希的数值
+    pub fn value(&self) -> u64 {
+        self.m_ctx.digest()
+    }
+}
+
+impl Default for Xxh64Hasher {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl HasherTrait for Xxh64Hasher {
+    const OUTPUT_LENGTH: usize = XXH64_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.value().to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Xxh64Hasher {
+    fn reset(&mut self) {
+        self.m_ctx.reset(self.m_seed);
+    }
+}
+
+/// XXH3（64位输出）哈希操作，非密码学哈希，结果按大端序输出（与xxhsum一致）
+#[derive(Clone)]
+pub struct Xxh3Hasher {
+    m_ctx: ExXxh3,
+}
+
+impl Xxh3Hasher {
+    /// 使用种子创建
+    pub fn with_seed(seed: u64) -> Self {
+        Xxh3Hasher {
+            m_ctx: ExXxh3::with_seed(seed),
+        }
+    }
+
+    /// 获取哈希的数值
+    pub fn value(&self) -> u64 {
+        self.m_ctx.digest()
+    }
+
+    /// 获取128位的哈希数值（即XXH3-128）
+    pub fn value128(&self) -> u128 {
+        self.m_ctx.digest128()
+    }
+}
+
+impl Default for Xxh3Hasher {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl HasherTrait for Xxh3Hasher {
+    const OUTPUT_LENGTH: usize = XXH3_OUTPUT_LENGTH;
+
+    fn update_message(&mut self, message: &str) {
+        self.m_ctx.update(message.as_bytes());
+    }
+
+    fn update_bytes(&mut self, vec: &[u8]) {
+        self.m_ctx.update(vec);
+    }
+
+    fn finalize(&self) -> ByteVector {
+        self.value().to_be_bytes().to_vec()
+    }
+
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.value().to_be_bytes());
+    }
+}
+
+impl ResettableHasherTrait for Xxh3Hasher {
+    fn reset(&mut self) {
+        self.m_ctx.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::xxhash_rust::xxh3::xxh3_64_with_seed;
+    use ::xxhash_rust::xxh32::xxh32;
+    use ::xxhash_rust::xxh64::xxh64;
+
+    #[test]
+    fn test_xxhash() {
+        // 空消息和"abc"在种子为0时的结果
+        let mut xxh32_hasher = Xxh32Hasher::default();
+        let mut xxh64_hasher = Xxh64Hasher::default();
+        let mut xxh3_hasher = Xxh3Hasher::default();
+        assert_eq!(xxh32_hasher.value(), 0x02CC5D05);
+        assert_eq!(xxh64_hasher.value(), 0xEF46DB3751D8E999);
+        assert_eq!(xxh3_hasher.value(), 0x2D06800538D394C2);
+        xxh32_hasher.update_message("abc");
+        xxh64_hasher.update_message("abc");
+        xxh3_hasher.update_message("abc");
+        assert_eq!(xxh32_hasher.value(), 0x32D153FF);
+        assert_eq!(xxh64_hasher.value(), 0x44BC2CF5AD770999);
+        assert_eq!(xxh3_hasher.value(), 0x78AF5F94892F3950);
+        assert_eq!(xxh32_hasher.finalize(), vec![0x32, 0xD1, 0x53, 0xFF]);
+    }
+
+    #[test]
+    fn test_xxhash_seed() {
+        // 分段输入带种子的结果与一次性计算相同
+        let data: ByteVector = (0..=255u8).cycle().take(1000).collect();
+        let mut xxh32_hasher = Xxh32Hasher::with_seed(0x9E3779B1);
+        let mut xxh64_hasher = Xxh64Hasher::with_seed(0x9E3779B185EBCA87);
+        let mut xxh3_hasher = Xxh3Hasher::with_seed(0x9E3779B185EBCA87);
+        for chunk in data.chunks(37) {
+            xxh32_hasher.update_bytes(chunk);
+            xxh64_hasher.update_bytes(chunk);
+            xxh3_hasher.update_bytes(chunk);
+        }
+        assert_eq!(xxh32_hasher.value(), xxh32(&data, 0x9E3779B1));
+        assert_eq!(xxh64_hasher.value(), xxh64(&data, 0x9E3779B185EBCA87));
+        assert_eq!(
+            xxh3_hasher.value(),
+            xxh3_64_with_seed(&data, 0x9E3779B185EBCA87)
+        );
+        assert_ne!(xxh64_hasher.value(), xxh64(&data, 0));
+    }
+}